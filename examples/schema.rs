@@ -4,10 +4,13 @@ use std::fs::create_dir_all;
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
 use cw_unity_prop::msg::{
-    ExecuteMsg, InstantiateMsg, QueryMsg, SudoMsg, WithdrawalReadyResponse,
+    ActionHistoryResponse, BeneficiariesResponse, ContractBalanceResponse, Cw20BalancesResponse,
+    ExecuteMsg, FreezeResponse, InstantiateMsg, MigrateMsg, PendingRewardsResponse,
+    PendingWithdrawAddressResponse, QueryMsg, SimulationResponse, SudoMsg,
+    WithdrawalApprovalsResponse, WithdrawalReadyResponse, WithdrawalStatusResponse,
     WithdrawalTimestampResponse,
 };
-use cw_unity_prop::state::Config;
+use cw_unity_prop::state::{Config, Totals};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -17,9 +20,21 @@ fn main() {
 
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
+    export_schema(&schema_for!(MigrateMsg), &out_dir);
     export_schema(&schema_for!(SudoMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
     export_schema(&schema_for!(Config), &out_dir);
     export_schema(&schema_for!(WithdrawalTimestampResponse), &out_dir);
     export_schema(&schema_for!(WithdrawalReadyResponse), &out_dir);
+    export_schema(&schema_for!(PendingWithdrawAddressResponse), &out_dir);
+    export_schema(&schema_for!(PendingRewardsResponse), &out_dir);
+    export_schema(&schema_for!(Cw20BalancesResponse), &out_dir);
+    export_schema(&schema_for!(ActionHistoryResponse), &out_dir);
+    export_schema(&schema_for!(Totals), &out_dir);
+    export_schema(&schema_for!(ContractBalanceResponse), &out_dir);
+    export_schema(&schema_for!(WithdrawalStatusResponse), &out_dir);
+    export_schema(&schema_for!(SimulationResponse), &out_dir);
+    export_schema(&schema_for!(BeneficiariesResponse), &out_dir);
+    export_schema(&schema_for!(WithdrawalApprovalsResponse), &out_dir);
+    export_schema(&schema_for!(FreezeResponse), &out_dir);
 }
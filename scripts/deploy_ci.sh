@@ -55,7 +55,7 @@ echo "clip hire initial neck maid actor venue client foam budget lock catalog sw
 INIT='{
   "native_denom": "'"$DENOM"'",
   "withdraw_address": "'"$1"'",
-  "withdraw_delay_in_days": 28
+  "withdraw_delay": { "time": 2419200 }
 }'
 echo "$INIT" | jq .
 
@@ -1,22 +1,44 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    coins, ensure_eq, to_binary, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
-    Response, StdError, StdResult, Timestamp, Uint128,
+    coin, coins, ensure_eq, to_json_binary, Addr, Api, BankMsg, Binary, Coin, Coins, CosmosMsg,
+    Deps, DepsMut, DistributionMsg, Empty, Env, IbcMsg, IbcTimeout, MessageInfo, Order, Response,
+    StakingMsg, StdError, StdResult, Storage, Timestamp, Uint128,
 };
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
+use cw20::{Cw20CoinVerified, Cw20Contract, Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::Bound;
+use cw_utils::{Duration, Expiration};
+use semver::Version;
 
 use crate::error::ContractError;
 use crate::msg::{
-    ExecuteMsg, InstantiateMsg, QueryMsg, SudoMsg, WithdrawalReadyResponse,
-    WithdrawalTimestampResponse,
+    ActionHistoryEntry, ActionHistoryResponse, BeneficiariesResponse, BeneficiaryInfo,
+    ContractBalanceResponse, Cw20BalancesResponse, EmergencyCouncilMsg, ExecuteMsg, FreezeResponse,
+    InstantiateMsg, MigrateMsg, PendingRewardsResponse, PendingWithdrawAddressResponse, QueryMsg,
+    SimulationResponse, SudoMsg, WithdrawalApprovalsResponse, WithdrawalReadyResponse,
+    WithdrawalStatus, WithdrawalStatusResponse, WithdrawalTimestampResponse,
+};
+use crate::simulation::SimulatedStorage;
+use crate::state::{
+    ActionKind, ActionRecord, Beneficiary, Config, ConfigV0_4, EmergencyCouncil, GuardianSet,
+    RewardsDestination, Totals, VestingSchedule, ACTION_COUNT, ACTION_HISTORY, BENEFICIARIES,
+    BENEFICIARIES_CLAIMED, CONFIG, CONFIG_V0_3, CONFIG_V0_4, CW20_TOKENS, FREEZE_COOLDOWN,
+    FROZEN_UNTIL, PENDING_WITHDRAW_ADDRESS, TOTALS, VESTING_CLAIMED, WITHDRAWAL_AMOUNT,
+    WITHDRAWAL_APPROVALS, WITHDRAWAL_READY, WITHDRAWAL_READY_V0_4, WITHDRAWAL_VETO,
 };
-use crate::state::{Config, CONFIG, WITHDRAWAL_READY};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:cw-unity-prop";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// pagination for the action history
+const DEFAULT_HISTORY_LIMIT: u32 = 10;
+const MAX_HISTORY_LIMIT: u32 = 30;
+
+// versions before v0.5 counted delays in days
+const SECONDS_PER_DAY: u64 = 86400;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -26,10 +48,63 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     let withdraw_address = deps.api.addr_validate(&msg.withdraw_address)?;
 
+    if msg.native_denom.is_empty() {
+        return Err(ContractError::InvalidNativeDenom {});
+    }
+
+    if let Some(schedule) = &msg.vesting {
+        if !schedule.is_valid() {
+            return Err(ContractError::InvalidVestingSchedule {});
+        }
+        VESTING_CLAIMED.save(deps.storage, &Uint128::zero())?;
+    }
+
+    if let Some(window) = &msg.withdraw_claim_window {
+        validate_claim_window(&msg.withdraw_delay, window)?;
+    }
+
+    let emergency_council = msg
+        .emergency_council
+        .map(|council| validate_emergency_council(deps.api, council))
+        .transpose()?;
+
+    if let Some(beneficiaries) = &msg.beneficiaries {
+        // vesting has a single schedule, with nothing to split
+        if beneficiaries.is_empty() || msg.vesting.is_some() {
+            return Err(ContractError::InvalidBeneficiaries {});
+        }
+
+        for beneficiary in beneficiaries {
+            let address = deps.api.addr_validate(&beneficiary.address)?;
+            if beneficiary.shares == 0 || BENEFICIARIES.has(deps.storage, &address) {
+                return Err(ContractError::InvalidBeneficiaries {});
+            }
+
+            BENEFICIARIES.save(
+                deps.storage,
+                &address,
+                &Beneficiary {
+                    shares: beneficiary.shares,
+                    claimed: Uint128::zero(),
+                    withdrawal_ready: None,
+                    veto: None,
+                },
+            )?;
+        }
+        BENEFICIARIES_CLAIMED.save(deps.storage, &Uint128::zero())?;
+    }
+
     let config = Config {
         withdraw_address: withdraw_address.clone(),
-        withdraw_delay_in_days: msg.withdraw_delay_in_days,
+        withdraw_delay: msg.withdraw_delay,
+        withdraw_claim_window: msg.withdraw_claim_window,
         native_denom: msg.native_denom,
+        vesting: msg.vesting,
+        rewards_destination: msg
+            .rewards_destination
+            .unwrap_or(RewardsDestination::Contract),
+        guardians: None,
+        emergency_council,
     };
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     CONFIG.save(deps.storage, &config)?;
@@ -37,7 +112,111 @@ pub fn instantiate(
     Ok(Response::new()
         .add_attribute("method", "instantiate")
         .add_attribute("withdraw_address", withdraw_address)
-        .add_attribute("withdraw_delay", msg.withdraw_delay_in_days.to_string()))
+        .add_attribute("withdraw_delay", msg.withdraw_delay.to_string()))
+}
+
+// a zero-length window would mean nothing is ever claimable,
+// and a window can only extend a ready point in the same unit
+fn validate_claim_window(delay: &Duration, window: &Duration) -> Result<(), ContractError> {
+    let is_zero = matches!(window, Duration::Height(0) | Duration::Time(0));
+    let same_unit = matches!(
+        (delay, window),
+        (Duration::Height(_), Duration::Height(_)) | (Duration::Time(_), Duration::Time(_))
+    );
+
+    match is_zero || !same_unit {
+        true => Err(ContractError::InvalidClaimWindow {}),
+        false => Ok(()),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+
+    // only ever migrate from this contract
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::CannotMigrate {
+            previous_contract: stored.contract,
+        });
+    }
+
+    // and never backwards
+    let previous_version: Version = stored.version.parse()?;
+    let new_version: Version = CONTRACT_VERSION.parse()?;
+    if previous_version > new_version {
+        return Err(ContractError::CannotMigrateVersion {
+            previous_version: stored.version,
+            new_version: CONTRACT_VERSION.to_string(),
+        });
+    }
+
+    // transform state one version at a time
+    if previous_version < Version::new(0, 4, 0) {
+        migrate_from_v0_3(deps.storage, &env)?;
+    }
+    if previous_version < Version::new(0, 5, 0) {
+        migrate_from_v0_4(deps.storage)?;
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "migrate")
+        .add_attribute("previous_version", stored.version)
+        .add_attribute("new_version", CONTRACT_VERSION))
+}
+
+// v0.3 has no vesting or claim window,
+// and never cleared a withdrawal once it was claimed
+fn migrate_from_v0_3(storage: &mut dyn Storage, env: &Env) -> Result<(), ContractError> {
+    let legacy_config = CONFIG_V0_3.load(storage)?;
+
+    let config = ConfigV0_4 {
+        withdraw_address: legacy_config.withdraw_address,
+        withdraw_delay_in_days: legacy_config.withdraw_delay_in_days,
+        withdraw_claim_window_in_days: None,
+        native_denom: legacy_config.native_denom,
+        vesting: None,
+        rewards_destination: RewardsDestination::Contract,
+    };
+    CONFIG_V0_4.save(storage, &config)?;
+
+    // a ready time in the past may already have been claimed,
+    // so it cannot be trusted to arm a withdrawal under the new rules
+    if let Some(ready_at) = WITHDRAWAL_READY_V0_4.may_load(storage)? {
+        if ready_at <= env.block.time {
+            WITHDRAWAL_READY_V0_4.remove(storage);
+        }
+    }
+
+    Ok(())
+}
+
+// v0.4 counts the delay and claim window in days,
+// and stores the ready time as a bare timestamp
+fn migrate_from_v0_4(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    let legacy_config = CONFIG_V0_4.load(storage)?;
+
+    let config = Config {
+        withdraw_address: legacy_config.withdraw_address,
+        withdraw_delay: Duration::Time(legacy_config.withdraw_delay_in_days * SECONDS_PER_DAY),
+        withdraw_claim_window: legacy_config
+            .withdraw_claim_window_in_days
+            .map(|window_in_days| Duration::Time(window_in_days * SECONDS_PER_DAY)),
+        native_denom: legacy_config.native_denom,
+        vesting: legacy_config.vesting,
+        rewards_destination: legacy_config.rewards_destination,
+        guardians: None,
+        emergency_council: None,
+    };
+    CONFIG.save(storage, &config)?;
+
+    if let Some(ready_at) = WITHDRAWAL_READY_V0_4.may_load(storage)? {
+        WITHDRAWAL_READY.save(storage, &Expiration::AtTime(ready_at))?;
+    }
+
+    Ok(())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -48,8 +227,17 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::StartWithdraw {} => start_withdraw(deps, env, info),
+        ExecuteMsg::StartWithdraw { amount } => start_withdraw(deps, env, info, amount),
         ExecuteMsg::ExecuteWithdraw {} => execute_withdraw(deps, env, info),
+        ExecuteMsg::CancelWithdraw {} => cancel_withdraw(deps, env, info),
+        ExecuteMsg::ApproveWithdrawal {} => approve_withdrawal(deps, env, info),
+        ExecuteMsg::Freeze {} => freeze(deps, env, info),
+        ExecuteMsg::ProposeNewWithdrawAddress { address } => {
+            propose_new_withdraw_address(deps, env, info, address)
+        }
+        ExecuteMsg::AcceptWithdrawAddress {} => accept_withdraw_address(deps, env, info),
+        ExecuteMsg::ClaimRewards {} => claim_rewards(deps, env, info),
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
     }
 }
 
@@ -59,11 +247,17 @@ pub fn start_withdraw(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    amount: Option<Vec<Coin>>,
 ) -> Result<Response, ContractError> {
     // get config
     let config = CONFIG.load(deps.storage)?;
     let withdraw_address = config.withdraw_address;
 
+    // with beneficiaries, each runs its own withdrawal
+    if !BENEFICIARIES.is_empty(deps.storage) {
+        return start_beneficiary_withdraw(deps, env, info, amount, config.withdraw_delay);
+    }
+
     // before continuing, only withdraw_address can call this
     ensure_eq!(
         info.sender,
@@ -71,24 +265,51 @@ pub fn start_withdraw(
         ContractError::Unauthorized {}
     );
 
-    // get number of days delay
-    let delay_in_days: u64 = config.withdraw_delay_in_days;
+    // with a vesting schedule, funds are released
+    // as they vest, so there is no timer to start
+    if config.vesting.is_some() {
+        return Err(ContractError::VestingScheduleActive {});
+    }
 
-    // do some really simple maths
-    let seconds_in_day = 86400u64;
-    let delay_in_seconds = delay_in_days * seconds_in_day;
+    // a partial withdrawal must actually ask for something
+    if let Some(amount) = &amount {
+        validate_amount(amount)?;
+    }
 
-    // when is 'now'?
-    let now: Timestamp = env.block.time;
+    // the delay runs from the current block,
+    // in either blocks or seconds
+    let withdrawal_ready_at = config.withdraw_delay.after(&env.block);
 
-    // calculate now + configured days (in seconds)
-    let rewards_ready_at: Timestamp = now.plus_seconds(delay_in_seconds);
+    WITHDRAWAL_READY.save(deps.storage, &withdrawal_ready_at)?;
 
-    WITHDRAWAL_READY.save(deps.storage, &rewards_ready_at)?;
+    // a fresh request is not bound by any earlier veto,
+    // nor does it carry over earlier approvals
+    WITHDRAWAL_VETO.remove(deps.storage);
+    WITHDRAWAL_APPROVALS.remove(deps.storage);
 
-    Ok(Response::new()
+    let mut res = Response::new()
         .add_attribute("action", "start_withdraw")
-        .add_attribute("withdrawal_ready_timestamp", rewards_ready_at.to_string()))
+        .add_attribute("withdrawal_ready_at", withdrawal_ready_at.to_string());
+
+    // a partial withdrawal, or everything?
+    match &amount {
+        Some(amount) => {
+            res = res.add_attribute("withdrawal_amount", coins_to_string(amount));
+            WITHDRAWAL_AMOUNT.save(deps.storage, amount)?;
+        }
+        None => WITHDRAWAL_AMOUNT.remove(deps.storage),
+    }
+
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            actor: Some(info.sender),
+            amount: amount.unwrap_or_default(),
+            ..ActionRecord::new(ActionKind::StartWithdraw, env.block.time)
+        },
+    )?;
+
+    Ok(res)
 }
 
 // this allows you to withdraw if the withdraw delay has passed
@@ -99,6 +320,17 @@ pub fn execute_withdraw(
 ) -> Result<Response, ContractError> {
     // get withdraw address
     let config = CONFIG.load(deps.storage)?;
+
+    // the emergency council can hold every kind of withdrawal
+    if frozen_until(deps.storage, &env)?.is_some() {
+        return Err(ContractError::Frozen {});
+    }
+
+    // with beneficiaries, each claims its own share
+    if !BENEFICIARIES.is_empty(deps.storage) {
+        return execute_beneficiary_withdraw(deps, env, info, config);
+    }
+
     let withdraw_address = config.withdraw_address;
 
     // before continuing, only withdraw_address can call this
@@ -108,26 +340,87 @@ pub fn execute_withdraw(
         ContractError::Unauthorized {}
     );
 
-    // this returns Vec<Coin> for the contract's holdings
-    let amount = deps.querier.query_all_balances(&env.contract.address)?;
+    // vesting replaces the all-or-nothing timer
+    if let Some(schedule) = config.vesting {
+        return execute_vested_withdraw(deps, env, withdraw_address, config.native_denom, schedule);
+    }
+
+    // get the point the withdrawal is ready
+    let withdrawal_ready_at = WITHDRAWAL_READY.load(deps.storage)?;
 
-    // get rewards ready timestamp
-    let withdrawal_ready_timestamp = WITHDRAWAL_READY.load(deps.storage)?;
+    // governance may have blocked this request
+    if let Some(reason) = WITHDRAWAL_VETO.may_load(deps.storage)? {
+        return Err(ContractError::WithdrawalVetoed { reason });
+    }
+
+    // a ready withdrawal is only claimable for so long
+    if is_withdrawal_expired(config.withdraw_claim_window, withdrawal_ready_at, &env)? {
+        return Err(ContractError::WithdrawalExpired {});
+    }
 
-    // check if we are after that time
-    let withdrawal_claimable = env.block.time > withdrawal_ready_timestamp;
+    // check if we have passed that point
+    let withdrawal_claimable = has_passed(&withdrawal_ready_at, &env);
 
     // dispatch Response or ContractError
     match withdrawal_claimable {
         true => {
+            // guardians, if any, must also have signed off
+            ensure_approved(deps.storage, &config.guardians)?;
+
+            // this returns Vec<Coin> for the contract's holdings
+            let contract_balances = deps.querier.query_all_balances(&env.contract.address)?;
+
+            // either the requested amount,
+            // or everything held by the contract, cw20 tokens included
+            let (amount, (cw20_amount, cw20_msgs)) =
+                match WITHDRAWAL_AMOUNT.may_load(deps.storage)? {
+                    Some(requested) => {
+                        // the balance may have shrunk since the request
+                        ensure_balances(&contract_balances, &requested)?;
+                        (requested, (vec![], vec![]))
+                    }
+                    None => (
+                        contract_balances,
+                        cw20_transfers(deps.as_ref(), &env, &withdraw_address)?,
+                    ),
+                };
+
+            // the request is used up,
+            // a further claim needs a fresh delay
+            WITHDRAWAL_READY.remove(deps.storage);
+            WITHDRAWAL_AMOUNT.remove(deps.storage);
+            WITHDRAWAL_APPROVALS.remove(deps.storage);
+
             // set up a bank send to the withdraw address
-            // from this contract
-            // for everything held by the contract
-            let msgs: Vec<CosmosMsg> = vec![BankMsg::Send {
-                to_address: withdraw_address.to_string(),
-                amount,
+            // from this contract, if it holds any bank balances
+            let mut msgs: Vec<CosmosMsg> = vec![];
+            if !amount.is_empty() {
+                msgs.push(
+                    BankMsg::Send {
+                        to_address: withdraw_address.to_string(),
+                        amount: amount.clone(),
+                    }
+                    .into(),
+                );
             }
-            .into()];
+            msgs.extend(cw20_msgs);
+
+            // there must be something to withdraw
+            if msgs.is_empty() {
+                return Err(ContractError::InsufficientContractBalance {});
+            }
+
+            add_to_total(deps.storage, &amount, |totals| &mut totals.withdrawn)?;
+            record_action(
+                deps.storage,
+                &ActionRecord {
+                    actor: Some(info.sender),
+                    amount,
+                    cw20_amount,
+                    recipient: Some(withdraw_address.to_string()),
+                    ..ActionRecord::new(ActionKind::ExecuteWithdraw, env.block.time)
+                },
+            )?;
 
             Ok(Response::new()
                 .add_attribute("action", "execute_withdraw")
@@ -138,178 +431,2239 @@ pub fn execute_withdraw(
     }
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
-    match msg {
-        SudoMsg::ExecuteBurn {} => execute_burn(deps, env),
-        SudoMsg::ExecuteSend { recipient, amount } => execute_send(deps, env, recipient, amount),
-        SudoMsg::ExecuteSendAll { recipient } => execute_send_all(deps, env, recipient),
+// the ready point plus the claim window
+// with no claim window configured, a withdrawal never expires
+fn withdrawal_expires_at(
+    claim_window: Option<Duration>,
+    withdrawal_ready_at: Expiration,
+) -> StdResult<Option<Expiration>> {
+    claim_window
+        .map(|window| withdrawal_ready_at + window)
+        .transpose()
+}
+
+// checks whether we have reached the end of the claim window
+fn is_withdrawal_expired(
+    claim_window: Option<Duration>,
+    withdrawal_ready_at: Expiration,
+    env: &Env,
+) -> StdResult<bool> {
+    match withdrawal_expires_at(claim_window, withdrawal_ready_at)? {
+        Some(expires_at) => Ok(has_passed(&expires_at, env)),
+        None => Ok(false),
     }
 }
 
-// this is the verbose way of doing this
-// but obvious reasons for making as easy-to-read as possible
-pub fn execute_burn(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
-    // this returns Vec<Coin>
-    // in this case for the contract's holdings
-    let amount: Vec<Coin> = deps.querier.query_all_balances(&env.contract.address)?;
+// strictly after, as the delay has always been counted:
+// a withdrawal ready at a point is claimable from the next block or second
+// unlike Expiration::is_expired, which is true at the point itself
+fn has_passed(expiration: &Expiration, env: &Env) -> bool {
+    match expiration {
+        Expiration::AtHeight(height) => env.block.height > *height,
+        Expiration::AtTime(time) => env.block.time > *time,
+        Expiration::Never {} => false,
+    }
+}
 
-    // create a burn msg struct
-    let burn_msg = BankMsg::Burn { amount };
+// counts approvals against the guardian threshold
+// with no guardians configured, nothing needs approving
+fn ensure_approved(
+    storage: &dyn Storage,
+    guardians: &Option<GuardianSet>,
+) -> Result<(), ContractError> {
+    if let Some((approvals, threshold)) = approvals_outstanding(storage, guardians)? {
+        return Err(ContractError::InsufficientApprovals {
+            approvals,
+            threshold,
+        });
+    }
 
-    // then msg we can add to Response
-    let msgs: Vec<CosmosMsg> = vec![burn_msg.into()];
+    Ok(())
+}
 
-    let res = Response::new()
-        .add_attribute("message_type", "sudo")
-        .add_attribute("action", "burn")
-        .add_messages(msgs);
-    Ok(res)
+// the approvals so far, and the threshold,
+// if the guardians have not yet signed off
+fn approvals_outstanding(
+    storage: &dyn Storage,
+    guardians: &Option<GuardianSet>,
+) -> StdResult<Option<(u32, u32)>> {
+    if let Some(guardians) = guardians {
+        let approvals = WITHDRAWAL_APPROVALS
+            .may_load(storage)?
+            .unwrap_or_default()
+            .len() as u32;
+        if approvals < guardians.threshold {
+            return Ok(Some((approvals, guardians.threshold)));
+        }
+    }
+
+    Ok(None)
 }
 
-// looks familiar?
-// yep, basically the same logic, isn't it
-// allows the community to send funds somewhere
-pub fn execute_send(
+// lets a guardian sign off on the pending withdrawal
+pub fn approve_withdrawal(
     deps: DepsMut,
     env: Env,
-    recipient: String,
-    amount: Uint128,
+    info: MessageInfo,
 ) -> Result<Response, ContractError> {
-    // get config
     let config = CONFIG.load(deps.storage)?;
 
-    // get native denom
-    let native_denom = config.native_denom;
+    // before continuing, only a guardian can call this
+    let is_guardian = match config.guardians {
+        Some(guardians) => guardians.members.contains(&info.sender),
+        None => false,
+    };
+    if !is_guardian {
+        return Err(ContractError::Unauthorized {});
+    }
 
-    // get contract balance
-    let contract_balances: Vec<Coin> = deps.querier.query_all_balances(&env.contract.address)?;
+    if WITHDRAWAL_READY.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::NoPendingWithdrawal {});
+    }
 
-    // we are going to check the contract has enough to execute the send
-    // now it should error if not,
-    // but we handle it here just in case
-    let native_balance = contract_balances
-        .iter()
-        .find(|&coin| coin.denom == native_denom);
+    let mut approvals = WITHDRAWAL_APPROVALS
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    if approvals.contains(&info.sender) {
+        return Err(ContractError::AlreadyApproved {});
+    }
+    approvals.push(info.sender.clone());
+    WITHDRAWAL_APPROVALS.save(deps.storage, &approvals)?;
 
-    // should never be no native balance, but handle it
-    // as well as insufficient funds case
-    if let Some(nb) = native_balance {
-        if nb.amount < amount {
-            return Err(ContractError::InsufficientContractBalance {});
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            actor: Some(info.sender.clone()),
+            ..ActionRecord::new(ActionKind::ApproveWithdrawal, env.block.time)
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "approve_withdrawal")
+        .add_attribute("guardian", info.sender)
+        .add_attribute("approvals", approvals.len().to_string()))
+}
+
+// lets the emergency council hold withdrawals
+// while governance decides what to do
+pub fn freeze(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // before continuing, only the emergency council can call this
+    let council = match config.emergency_council {
+        Some(council) if council.address == info.sender => council,
+        _ => return Err(ContractError::Unauthorized {}),
+    };
+
+    // a freeze cannot be extended, and once it ends
+    // the council has to wait out the duration again,
+    // so that it cannot hold withdrawals indefinitely
+    if frozen_until(deps.storage, &env)?.is_some() {
+        return Err(ContractError::AlreadyFrozen {});
+    }
+    if let Some(until) = FREEZE_COOLDOWN.may_load(deps.storage)? {
+        if !until.is_expired(&env.block) {
+            return Err(ContractError::FreezeCooldown { until });
         }
-    } else {
-        return Err(ContractError::NoNativeBalance {});
     }
 
-    // validate supplied address
-    let validated_address = deps.api.addr_validate(&recipient)?;
+    let frozen_until = council.freeze_duration.after(&env.block);
+    FROZEN_UNTIL.save(deps.storage, &frozen_until)?;
+    FREEZE_COOLDOWN.save(deps.storage, &(frozen_until + council.freeze_duration)?)?;
 
-    // create coins vec
-    let amount: Vec<Coin> = coins(amount.u128(), native_denom);
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            actor: Some(info.sender),
+            ..ActionRecord::new(ActionKind::Freeze, env.block.time)
+        },
+    )?;
 
-    // create a send
-    let send_msg = BankMsg::Send {
-        to_address: validated_address.to_string(),
-        amount,
-    };
+    Ok(Response::new()
+        .add_attribute("action", "freeze")
+        .add_attribute("frozen_until", frozen_until.to_string()))
+}
 
-    // then msg we can add to Response
-    let msgs: Vec<CosmosMsg> = vec![send_msg.into()];
+// a freeze lifts itself once its duration has passed
+fn frozen_until(storage: &dyn Storage, env: &Env) -> StdResult<Option<Expiration>> {
+    let frozen_until = FROZEN_UNTIL.may_load(storage)?;
+    Ok(frozen_until.filter(|frozen_until| !frozen_until.is_expired(&env.block)))
+}
 
-    let res = Response::new()
-        .add_attribute("message_type", "sudo")
-        .add_attribute("action", "send")
-        .add_messages(msgs);
-    Ok(res)
+fn validate_emergency_council(
+    api: &dyn Api,
+    council: EmergencyCouncilMsg,
+) -> Result<EmergencyCouncil, ContractError> {
+    // a zero-length freeze would lift as soon as it was made
+    if matches!(
+        council.freeze_duration,
+        Duration::Height(0) | Duration::Time(0)
+    ) {
+        return Err(ContractError::InvalidFreezeDuration {});
+    }
+
+    Ok(EmergencyCouncil {
+        address: api.addr_validate(&council.address)?,
+        freeze_duration: council.freeze_duration,
+    })
 }
 
-// a mashup of the above two handlers, really
-pub fn execute_send_all(
+// lets the withdraw address retract a withdrawal it started
+pub fn cancel_withdraw(
     deps: DepsMut,
     env: Env,
-    recipient: String,
+    info: MessageInfo,
 ) -> Result<Response, ContractError> {
     // get config
     let config = CONFIG.load(deps.storage)?;
+    let withdraw_address = config.withdraw_address;
 
-    // get native denom
-    let native_denom = config.native_denom;
+    // with beneficiaries, each can only cancel its own
+    if !BENEFICIARIES.is_empty(deps.storage) {
+        return cancel_beneficiary_withdraw(deps, env, info);
+    }
 
-    // get contract balance
-    let contract_balances: Vec<Coin> = deps.querier.query_all_balances(&env.contract.address)?;
+    // before continuing, only withdraw_address can call this
+    ensure_eq!(
+        info.sender,
+        withdraw_address,
+        ContractError::Unauthorized {}
+    );
 
-    // get native balance
-    let native_balance = contract_balances
-        .iter()
-        .find(|&coin| coin.denom == native_denom);
+    clear_pending_withdrawal(deps.storage)?;
 
-    // handle no native balance
-    if native_balance.is_none() {
-        return Err(ContractError::NoNativeBalance {});
-    }
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            actor: Some(info.sender),
+            ..ActionRecord::new(ActionKind::CancelWithdraw, env.block.time)
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_withdraw")
+        .add_attribute("withdraw_address", withdraw_address))
+}
+
+// first half of a handover
+// the nominee has to accept before anything changes
+pub fn propose_new_withdraw_address(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    // get config
+    let config = CONFIG.load(deps.storage)?;
+    let withdraw_address = config.withdraw_address;
+
+    // before continuing, only withdraw_address can call this
+    ensure_eq!(
+        info.sender,
+        withdraw_address,
+        ContractError::Unauthorized {}
+    );
 
     // validate supplied address
-    let validated_address = deps.api.addr_validate(&recipient)?;
+    let validated_address = deps.api.addr_validate(&address)?;
 
-    // create a send
-    // note that this sends all balances
-    // not just native
-    let send_msg = BankMsg::Send {
-        to_address: validated_address.to_string(),
-        amount: contract_balances,
-    };
+    // replaces any earlier nomination
+    PENDING_WITHDRAW_ADDRESS.save(deps.storage, &validated_address)?;
 
-    // marshall message
-    let msgs: Vec<CosmosMsg> = vec![send_msg.into()];
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            actor: Some(info.sender),
+            recipient: Some(validated_address.to_string()),
+            ..ActionRecord::new(ActionKind::ProposeNewWithdrawAddress, env.block.time)
+        },
+    )?;
 
-    let res = Response::new()
-        .add_attribute("message_type", "sudo")
-        .add_attribute("action", "send_all")
-        .add_messages(msgs);
-    Ok(res)
+    Ok(Response::new()
+        .add_attribute("action", "propose_new_withdraw_address")
+        .add_attribute("withdraw_address", withdraw_address)
+        .add_attribute("pending_withdraw_address", validated_address))
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::GetConfig {} => to_binary(&query_config(deps)?),
-        QueryMsg::GetWithdrawalReadyTime {} => to_binary(&get_withdraw_ready(deps)?),
-        QueryMsg::IsWithdrawalReady {} => to_binary(&query_withdraw_ready(deps, env)?),
-    }
-}
+// second half of a handover
+// any in-flight withdrawal is cleared, so the delay cannot be shortcut
+pub fn accept_withdraw_address(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let pending_withdraw_address = PENDING_WITHDRAW_ADDRESS
+        .may_load(deps.storage)?
+        .ok_or(ContractError::Unauthorized {})?;
 
-fn query_config(deps: Deps) -> StdResult<Config> {
-    let config = CONFIG.load(deps.storage)?;
-    Ok(config)
-}
+    // before continuing, only the nominee can call this
+    ensure_eq!(
+        info.sender,
+        pending_withdraw_address,
+        ContractError::Unauthorized {}
+    );
 
-fn get_withdraw_ready(deps: Deps) -> StdResult<WithdrawalTimestampResponse> {
-    let withdrawal_ready_timestamp = WITHDRAWAL_READY.may_load(deps.storage)?;
-    match withdrawal_ready_timestamp {
-        Some(wrt) => Ok(WithdrawalTimestampResponse {
-            withdrawal_ready_timestamp: wrt,
-        }),
-        None => Err(StdError::not_found(
-            "Withdrawal not yet requested - no Withdrawal time exists",
-        )),
-    }
-}
+    let mut config = CONFIG.load(deps.storage)?;
+    let previous_withdraw_address = config.withdraw_address;
+    config.withdraw_address = pending_withdraw_address.clone();
+    CONFIG.save(deps.storage, &config)?;
 
-fn query_withdraw_ready(deps: Deps, env: Env) -> StdResult<WithdrawalReadyResponse> {
-    let withdrawal_ready_timestamp = WITHDRAWAL_READY.may_load(deps.storage)?;
+    PENDING_WITHDRAW_ADDRESS.remove(deps.storage);
+    reset_pending_withdrawal(deps.storage);
 
-    match withdrawal_ready_timestamp {
-        Some(wrt) => {
-            // check if we are have passed the point where withdrawal is possible
-            let is_withdrawal_ready = env.block.time > wrt;
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            actor: Some(info.sender),
+            ..ActionRecord::new(ActionKind::AcceptWithdrawAddress, env.block.time)
+        },
+    )?;
 
-            Ok(WithdrawalReadyResponse {
-                is_withdrawal_ready,
-            })
-        }
-        None => Err(StdError::not_found(
-            "Withdrawal not yet requested - no Withdrawal time exists",
+    Ok(Response::new()
+        .add_attribute("action", "accept_withdraw_address")
+        .add_attribute("old_withdraw_address", previous_withdraw_address)
+        .add_attribute("new_withdraw_address", pending_withdraw_address))
+}
+
+// anyone can trigger this, as the destination is fixed by config
+pub fn claim_rewards(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // get config
+    let config = CONFIG.load(deps.storage)?;
+
+    let delegations = deps.querier.query_all_delegations(&env.contract.address)?;
+    if delegations.is_empty() {
+        return Err(ContractError::NoDelegations {});
+    }
+
+    // withdraw from every validator,
+    // adding up what we expect to receive as we go
+    let rewards = pending_rewards(deps.as_ref(), &env)?;
+    let mut msgs: Vec<CosmosMsg> = delegations
+        .into_iter()
+        .map(|delegation| {
+            DistributionMsg::WithdrawDelegatorReward {
+                validator: delegation.validator,
+            }
+            .into()
+        })
+        .collect();
+
+    // rewards land in the contract, so route them on from there
+    let mut recipient = None;
+    if !rewards.is_empty() {
+        match config.rewards_destination {
+            RewardsDestination::Contract => {}
+            RewardsDestination::WithdrawAddress => {
+                recipient = Some(config.withdraw_address.to_string());
+                msgs.push(
+                    BankMsg::Send {
+                        to_address: config.withdraw_address.to_string(),
+                        amount: rewards.clone(),
+                    }
+                    .into(),
+                )
+            }
+            RewardsDestination::CommunityPool => msgs.push(
+                DistributionMsg::FundCommunityPool {
+                    amount: rewards.clone(),
+                }
+                .into(),
+            ),
+        }
+    }
+
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            actor: Some(info.sender),
+            amount: rewards.clone(),
+            recipient,
+            ..ActionRecord::new(ActionKind::ClaimRewards, env.block.time)
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "claim_rewards")
+        .add_attribute(
+            "rewards_destination",
+            config.rewards_destination.to_string(),
+        )
+        .add_attribute("rewards", coins_to_string(&rewards))
+        .add_messages(msgs))
+}
+
+// cw20 tokens arrive via the token contract, i.e. info.sender
+// anyone can send tokens, we just need to remember which
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    // anyone can also call this directly,
+    // so only record a sender that answers as a token
+    // it is only swept by a full withdrawal once governance tracks it
+    Cw20Contract(info.sender.clone())
+        .meta(&deps.querier)
+        .map_err(|_| ContractError::NotCw20Token {})?;
+
+    // the actor is whoever sent the tokens, not the token contract
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            actor: Some(deps.api.addr_validate(&msg.sender)?),
+            cw20_amount: vec![Cw20CoinVerified {
+                address: info.sender.clone(),
+                amount: msg.amount,
+            }],
+            ..ActionRecord::new(ActionKind::ReceiveCw20, env.block.time)
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "receive_cw20")
+        .add_attribute("token", info.sender)
+        .add_attribute("sender", msg.sender)
+        .add_attribute("amount", msg.amount))
+}
+
+// the contract's holdings of every tracked cw20 token
+// a token that fails to answer is left out, rather than failing
+// every withdrawal, until governance untracks it
+fn cw20_balances(deps: Deps, env: &Env) -> StdResult<Vec<Cw20CoinVerified>> {
+    let tokens = CW20_TOKENS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<Addr>>>()?;
+
+    let balances = tokens
+        .into_iter()
+        .filter_map(|address| {
+            let amount = Cw20Contract(address.clone())
+                .balance(&deps.querier, &env.contract.address)
+                .ok()?;
+            Some(Cw20CoinVerified { address, amount })
+        })
+        .collect();
+
+    Ok(balances)
+}
+
+// every non-zero cw20 balance, and transfers of them to `recipient`
+fn cw20_transfers(
+    deps: Deps,
+    env: &Env,
+    recipient: &Addr,
+) -> StdResult<(Vec<Cw20CoinVerified>, Vec<CosmosMsg>)> {
+    let balances: Vec<Cw20CoinVerified> = cw20_balances(deps, env)?
+        .into_iter()
+        .filter(|balance| !balance.amount.is_zero())
+        .collect();
+
+    let msgs = balances
+        .iter()
+        .map(|balance| {
+            Cw20Contract(balance.address.clone()).call(Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: balance.amount,
+            })
+        })
+        .collect::<StdResult<Vec<CosmosMsg>>>()?;
+
+    Ok((balances, msgs))
+}
+
+// adds `amount` to one of the running totals
+fn add_to_total(
+    storage: &mut dyn Storage,
+    amount: &[Coin],
+    total: fn(&mut Totals) -> &mut Vec<Coin>,
+) -> StdResult<()> {
+    let mut totals = TOTALS.may_load(storage)?.unwrap_or_default();
+
+    let mut sum = Coins::default();
+    for coin in total(&mut totals).iter().chain(amount) {
+        sum.add(coin.clone())?;
+    }
+    *total(&mut totals) = sum.into_vec();
+
+    TOTALS.save(storage, &totals)
+}
+
+// appends to the action history, which is never rewritten
+fn record_action(storage: &mut dyn Storage, record: &ActionRecord) -> StdResult<()> {
+    let id = ACTION_COUNT.may_load(storage)?.unwrap_or_default();
+    ACTION_HISTORY.save(storage, id, record)?;
+    ACTION_COUNT.save(storage, &(id + 1))
+}
+
+// sums the rewards accumulated across every delegation
+fn pending_rewards(deps: Deps, env: &Env) -> StdResult<Vec<Coin>> {
+    let delegations = deps.querier.query_all_delegations(&env.contract.address)?;
+
+    // the summary delegation has no rewards,
+    // so we need the full delegation for each validator
+    let mut rewards = Coins::default();
+    for delegation in delegations {
+        let full_delegation = deps
+            .querier
+            .query_delegation(&env.contract.address, &delegation.validator)?;
+        if let Some(full_delegation) = full_delegation {
+            for reward in full_delegation.accumulated_rewards {
+                rewards.add(reward)?;
+            }
+        }
+    }
+
+    Ok(rewards.into_vec())
+}
+
+// removes the pending withdrawal
+// erroring if there was nothing to remove
+fn clear_pending_withdrawal(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    if WITHDRAWAL_READY.may_load(storage)?.is_none() {
+        return Err(ContractError::NoPendingWithdrawal {});
+    }
+
+    reset_pending_withdrawal(storage);
+
+    Ok(())
+}
+
+// removes the pending withdrawal, if there is one
+fn reset_pending_withdrawal(storage: &mut dyn Storage) {
+    WITHDRAWAL_READY.remove(storage);
+    WITHDRAWAL_AMOUNT.remove(storage);
+    WITHDRAWAL_VETO.remove(storage);
+    WITHDRAWAL_APPROVALS.remove(storage);
+}
+
+// claims whatever portion of the native denom
+// has vested and not yet been claimed
+fn execute_vested_withdraw(
+    deps: DepsMut,
+    env: Env,
+    withdraw_address: Addr,
+    native_denom: String,
+    schedule: VestingSchedule,
+) -> Result<Response, ContractError> {
+    let claimable = vesting_claimable(deps.as_ref(), &env, &native_denom, &schedule)?;
+
+    if claimable.is_zero() {
+        return Err(ContractError::NothingVested {});
+    }
+
+    // record the claim before sending
+    VESTING_CLAIMED.update(deps.storage, |claimed| -> StdResult<_> {
+        Ok(claimed + claimable)
+    })?;
+
+    let amount = coins(claimable.u128(), &native_denom);
+    add_to_total(deps.storage, &amount, |totals| &mut totals.withdrawn)?;
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            actor: Some(withdraw_address.clone()),
+            amount,
+            recipient: Some(withdraw_address.to_string()),
+            ..ActionRecord::new(ActionKind::ExecuteWithdraw, env.block.time)
+        },
+    )?;
+
+    let msgs: Vec<CosmosMsg> = vec![BankMsg::Send {
+        to_address: withdraw_address.to_string(),
+        amount: coins(claimable.u128(), native_denom),
+    }
+    .into()];
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_withdraw")
+        .add_attribute("withdraw_address", withdraw_address)
+        .add_attribute("vested_amount", claimable)
+        .add_messages(msgs))
+}
+
+// each beneficiary runs its own timer
+fn start_beneficiary_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Option<Vec<Coin>>,
+    withdraw_delay: Duration,
+) -> Result<Response, ContractError> {
+    let mut beneficiary = BENEFICIARIES
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::Unauthorized {})?;
+
+    // the share is worked out when it is claimed
+    if amount.is_some() {
+        return Err(ContractError::BeneficiaryPartialWithdrawal {});
+    }
+
+    // a fresh request is not bound by any earlier veto
+    let withdrawal_ready_at = withdraw_delay.after(&env.block);
+    beneficiary.withdrawal_ready = Some(withdrawal_ready_at);
+    beneficiary.veto = None;
+    BENEFICIARIES.save(deps.storage, &info.sender, &beneficiary)?;
+
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            actor: Some(info.sender.clone()),
+            ..ActionRecord::new(ActionKind::StartWithdraw, env.block.time)
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "start_withdraw")
+        .add_attribute("beneficiary", info.sender)
+        .add_attribute("withdrawal_ready_at", withdrawal_ready_at.to_string()))
+}
+
+fn execute_beneficiary_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    config: Config,
+) -> Result<Response, ContractError> {
+    let mut beneficiary = BENEFICIARIES
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::Unauthorized {})?;
+
+    let withdrawal_ready_at = beneficiary
+        .withdrawal_ready
+        .ok_or(ContractError::NoPendingWithdrawal {})?;
+
+    // the same veto and timer rules as a single withdraw_address
+    if let Some(reason) = beneficiary.veto {
+        return Err(ContractError::WithdrawalVetoed { reason });
+    }
+    if is_withdrawal_expired(config.withdraw_claim_window, withdrawal_ready_at, &env)? {
+        return Err(ContractError::WithdrawalExpired {});
+    }
+    if !has_passed(&withdrawal_ready_at, &env) {
+        return Err(ContractError::WithdrawalNotReady {});
+    }
+
+    let pool = beneficiary_pool(deps.as_ref(), &env, &config.native_denom)?;
+    let claimable = beneficiary_claimable(&beneficiary, &pool);
+
+    if claimable.is_zero() {
+        return Err(ContractError::InsufficientContractBalance {});
+    }
+
+    // record the claim before sending,
+    // a further claim needs a fresh delay
+    beneficiary.claimed += claimable;
+    beneficiary.withdrawal_ready = None;
+    BENEFICIARIES.save(deps.storage, &info.sender, &beneficiary)?;
+    BENEFICIARIES_CLAIMED.update(deps.storage, |claimed| -> StdResult<_> {
+        Ok(claimed + claimable)
+    })?;
+
+    let amount = coins(claimable.u128(), &config.native_denom);
+    add_to_total(deps.storage, &amount, |totals| &mut totals.withdrawn)?;
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            actor: Some(info.sender.clone()),
+            amount: amount.clone(),
+            recipient: Some(info.sender.to_string()),
+            ..ActionRecord::new(ActionKind::ExecuteWithdraw, env.block.time)
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_withdraw")
+        .add_attribute("beneficiary", &info.sender)
+        .add_attribute("amount", claimable)
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount,
+        }))
+}
+
+fn cancel_beneficiary_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut beneficiary = BENEFICIARIES
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::Unauthorized {})?;
+
+    if beneficiary.withdrawal_ready.is_none() {
+        return Err(ContractError::NoPendingWithdrawal {});
+    }
+
+    beneficiary.withdrawal_ready = None;
+    beneficiary.veto = None;
+    BENEFICIARIES.save(deps.storage, &info.sender, &beneficiary)?;
+
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            actor: Some(info.sender.clone()),
+            ..ActionRecord::new(ActionKind::CancelWithdraw, env.block.time)
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_withdraw")
+        .add_attribute("beneficiary", info.sender))
+}
+
+// everything the beneficiaries have had a claim on,
+// i.e. the native balance plus what they have already claimed,
+// along with the balance still held and the total shares it is split by
+struct BeneficiaryPool {
+    total: Uint128,
+    balance: Uint128,
+    total_shares: u64,
+}
+
+fn beneficiary_pool(deps: Deps, env: &Env, native_denom: &str) -> StdResult<BeneficiaryPool> {
+    let claimed = BENEFICIARIES_CLAIMED
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let balance = deps
+        .querier
+        .query_balance(&env.contract.address, native_denom)?
+        .amount;
+
+    let total_shares = BENEFICIARIES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, beneficiary)| beneficiary.shares))
+        .sum::<StdResult<u64>>()?;
+
+    Ok(BeneficiaryPool {
+        total: balance + claimed,
+        balance,
+        total_shares,
+    })
+}
+
+// a pro-rata share of the pool, less what has already been claimed
+// so it does not matter who claims first
+// governance may have moved funds out since an earlier claim,
+// so a claim never exceeds what is held, and the rest stays owed
+fn beneficiary_claimable(beneficiary: &Beneficiary, pool: &BeneficiaryPool) -> Uint128 {
+    pool.total
+        .multiply_ratio(beneficiary.shares, pool.total_shares)
+        .saturating_sub(beneficiary.claimed)
+        .min(pool.balance)
+}
+
+// the total under vesting is whatever is held now
+// plus whatever has already been claimed
+fn vesting_claimable(
+    deps: Deps,
+    env: &Env,
+    native_denom: &str,
+    schedule: &VestingSchedule,
+) -> StdResult<Uint128> {
+    let claimed = VESTING_CLAIMED.may_load(deps.storage)?.unwrap_or_default();
+    let native_balance = deps
+        .querier
+        .query_balance(&env.contract.address, native_denom)?
+        .amount;
+
+    let vested = schedule.vested_amount(env.block.time, native_balance + claimed);
+
+    Ok(vested.saturating_sub(claimed))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    match msg {
+        SudoMsg::ExecuteBurn { amount } => execute_burn(deps, env, amount),
+        SudoMsg::ExecuteSend {
+            recipient,
+            amount,
+            denom,
+        } => execute_send(deps, env, recipient, amount, denom),
+        SudoMsg::ExecuteSendAll { recipient } => execute_send_all(deps, env, recipient),
+        SudoMsg::ExecuteSplitSend { recipients } => execute_split_send(deps, env, recipients),
+        SudoMsg::CancelWithdraw { beneficiary } => sudo_cancel_withdraw(deps, env, beneficiary),
+        SudoMsg::VetoWithdrawal {
+            reason,
+            beneficiary,
+        } => veto_withdrawal(deps, env, reason, beneficiary),
+        SudoMsg::SetGuardians {
+            guardians,
+            threshold,
+        } => set_guardians(deps, env, guardians, threshold),
+        SudoMsg::Unfreeze {} => unfreeze(deps, env),
+        SudoMsg::SetEmergencyCouncil { council } => set_emergency_council(deps, env, council),
+        SudoMsg::UpdateConfig {
+            withdraw_address,
+            withdraw_delay,
+            native_denom,
+            rewards_destination,
+        } => update_config(
+            deps,
+            env,
+            withdraw_address,
+            withdraw_delay,
+            native_denom,
+            rewards_destination,
+        ),
+        SudoMsg::ExecuteSendCw20 {
+            token,
+            recipient,
+            amount,
+        } => execute_send_cw20(deps, env, token, recipient, amount),
+        SudoMsg::BurnCw20 { token, amount } => execute_burn_cw20(deps, env, token, amount),
+        SudoMsg::TrackCw20 { token } => track_cw20(deps, env, token),
+        SudoMsg::UntrackCw20 { token } => untrack_cw20(deps, env, token),
+        SudoMsg::IbcTransfer {
+            channel_id,
+            to_address,
+            amount,
+            timeout_seconds,
+        } => execute_ibc_transfer(deps, env, channel_id, to_address, amount, timeout_seconds),
+        SudoMsg::FundCommunityPool { amount } => execute_fund_community_pool(deps, env, amount),
+        SudoMsg::Delegate { validator, amount } => execute_delegate(deps, env, validator, amount),
+        SudoMsg::Undelegate { validator, amount } => {
+            execute_undelegate(deps, env, validator, amount)
+        }
+        SudoMsg::Redelegate {
+            src_validator,
+            dst_validator,
+            amount,
+        } => execute_redelegate(deps, env, src_validator, dst_validator, amount),
+    }
+}
+
+// this is the verbose way of doing this
+// but obvious reasons for making as easy-to-read as possible
+pub fn execute_burn(
+    deps: DepsMut,
+    env: Env,
+    amount: Option<Vec<Coin>>,
+) -> Result<Response, ContractError> {
+    // this returns Vec<Coin>
+    // in this case for the contract's holdings
+    let contract_balances: Vec<Coin> = deps.querier.query_all_balances(&env.contract.address)?;
+
+    // if governance named an amount, it must be held by the contract
+    // otherwise we burn the lot
+    let amount = match amount {
+        Some(amount) => {
+            validate_amount(&amount)?;
+            ensure_balances(&contract_balances, &amount)?;
+            amount
+        }
+        None => contract_balances,
+    };
+
+    // record what is being burned
+    let burned = coins_to_string(&amount);
+    add_to_total(deps.storage, &amount, |totals| &mut totals.burned)?;
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            amount: amount.clone(),
+            ..ActionRecord::new(ActionKind::Burn, env.block.time)
+        },
+    )?;
+
+    // create a burn msg struct
+    let burn_msg = BankMsg::Burn { amount };
+
+    // then msg we can add to Response
+    let msgs: Vec<CosmosMsg> = vec![burn_msg.into()];
+
+    let res = Response::new()
+        .add_attribute("message_type", "sudo")
+        .add_attribute("action", "burn")
+        .add_attribute("amount", burned)
+        .add_messages(msgs);
+    Ok(res)
+}
+
+// looks familiar?
+// yep, basically the same logic, isn't it
+// allows the community to send funds somewhere
+pub fn execute_send(
+    deps: DepsMut,
+    env: Env,
+    recipient: String,
+    amount: Uint128,
+    denom: Option<String>,
+) -> Result<Response, ContractError> {
+    // get config
+    let config = CONFIG.load(deps.storage)?;
+
+    // either the denom asked for, e.g. an ibc or tokenfactory denom,
+    // or the native denom
+    let denom = denom.unwrap_or(config.native_denom);
+
+    // we are going to check the contract has enough to execute the send
+    // now it should error if not,
+    // but we handle it here just in case
+    ensure_denom_balance(deps.as_ref(), &env, &denom, amount)?;
+
+    // validate supplied address
+    let validated_address = deps.api.addr_validate(&recipient)?;
+
+    // create coins vec
+    let amount: Vec<Coin> = coins(amount.u128(), denom);
+
+    add_to_total(deps.storage, &amount, |totals| &mut totals.sent)?;
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            amount: amount.clone(),
+            recipient: Some(validated_address.to_string()),
+            ..ActionRecord::new(ActionKind::Send, env.block.time)
+        },
+    )?;
+
+    // create a send
+    let send_msg = BankMsg::Send {
+        to_address: validated_address.to_string(),
+        amount,
+    };
+
+    // then msg we can add to Response
+    let msgs: Vec<CosmosMsg> = vec![send_msg.into()];
+
+    let res = Response::new()
+        .add_attribute("message_type", "sudo")
+        .add_attribute("action", "send")
+        .add_messages(msgs);
+    Ok(res)
+}
+
+// checks the contract holds at least `amount` of the native denom
+fn ensure_native_balance(
+    deps: Deps,
+    env: &Env,
+    native_denom: &str,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    // get contract balance
+    let contract_balances: Vec<Coin> = deps.querier.query_all_balances(&env.contract.address)?;
+
+    let native_balance = contract_balances
+        .iter()
+        .find(|&coin| coin.denom == native_denom);
+
+    // should never be no native balance, but handle it
+    // as well as insufficient funds case
+    match native_balance {
+        Some(nb) if nb.amount < amount => Err(ContractError::InsufficientContractBalance {}),
+        Some(_) => Ok(()),
+        None => Err(ContractError::NoNativeBalance {}),
+    }
+}
+
+// checks the contract holds at least `amount` of `denom`
+// reporting by how much it falls short if not
+fn ensure_denom_balance(
+    deps: Deps,
+    env: &Env,
+    denom: &str,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let balance = deps.querier.query_balance(&env.contract.address, denom)?;
+
+    if balance.amount < amount {
+        return Err(ContractError::InsufficientDenomBalance {
+            denom: denom.to_string(),
+            shortfall: amount - balance.amount,
+        });
+    }
+    Ok(())
+}
+
+// a mashup of the above two handlers, really
+pub fn execute_send_all(
+    deps: DepsMut,
+    env: Env,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    // get config
+    let config = CONFIG.load(deps.storage)?;
+
+    // get native denom
+    let native_denom = config.native_denom;
+
+    // get contract balance
+    let contract_balances: Vec<Coin> = deps.querier.query_all_balances(&env.contract.address)?;
+
+    // get native balance
+    let native_balance = contract_balances
+        .iter()
+        .find(|&coin| coin.denom == native_denom);
+
+    // handle no native balance
+    if native_balance.is_none() {
+        return Err(ContractError::NoNativeBalance {});
+    }
+
+    // validate supplied address
+    let validated_address = deps.api.addr_validate(&recipient)?;
+
+    // create a send
+    // note that this sends all balances
+    // not just native
+    let send_msg = BankMsg::Send {
+        to_address: validated_address.to_string(),
+        amount: contract_balances.clone(),
+    };
+
+    // cw20 tokens go too
+    let (cw20_amount, cw20_msgs) = cw20_transfers(deps.as_ref(), &env, &validated_address)?;
+
+    add_to_total(deps.storage, &contract_balances, |totals| &mut totals.sent)?;
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            amount: contract_balances,
+            cw20_amount,
+            recipient: Some(validated_address.to_string()),
+            ..ActionRecord::new(ActionKind::SendAll, env.block.time)
+        },
+    )?;
+
+    // marshall message
+    let mut msgs: Vec<CosmosMsg> = vec![send_msg.into()];
+    msgs.extend(cw20_msgs);
+
+    let res = Response::new()
+        .add_attribute("message_type", "sudo")
+        .add_attribute("action", "send_all")
+        .add_messages(msgs);
+    Ok(res)
+}
+
+// like execute_send, but to several recipients at once
+// if any part is invalid, nothing is sent
+pub fn execute_split_send(
+    deps: DepsMut,
+    env: Env,
+    recipients: Vec<(String, Uint128)>,
+) -> Result<Response, ContractError> {
+    // get config
+    let config = CONFIG.load(deps.storage)?;
+
+    // get native denom
+    let native_denom = config.native_denom;
+
+    // every recipient must actually receive something
+    if recipients.is_empty() || recipients.iter().any(|(_, amount)| amount.is_zero()) {
+        return Err(ContractError::InvalidAmount {});
+    }
+
+    // the contract must be able to cover all of the sends together
+    let total = recipients
+        .iter()
+        .try_fold(Uint128::zero(), |total, (_, amount)| {
+            total.checked_add(*amount)
+        })
+        .map_err(StdError::from)?;
+    ensure_native_balance(deps.as_ref(), &env, &native_denom, total)?;
+
+    // validate every address before building any sends
+    let validated_recipients = recipients
+        .into_iter()
+        .map(|(recipient, amount)| Ok((deps.api.addr_validate(&recipient)?, amount)))
+        .collect::<StdResult<Vec<(Addr, Uint128)>>>()?;
+
+    let mut msgs: Vec<BankMsg> = vec![];
+    for (validated_address, amount) in validated_recipients {
+        let amount = coins(amount.u128(), &native_denom);
+
+        // one record per recipient
+        add_to_total(deps.storage, &amount, |totals| &mut totals.sent)?;
+        record_action(
+            deps.storage,
+            &ActionRecord {
+                amount: amount.clone(),
+                recipient: Some(validated_address.to_string()),
+                ..ActionRecord::new(ActionKind::SplitSend, env.block.time)
+            },
+        )?;
+
+        msgs.push(BankMsg::Send {
+            to_address: validated_address.to_string(),
+            amount,
+        });
+    }
+
+    let res = Response::new()
+        .add_attribute("message_type", "sudo")
+        .add_attribute("action", "split_send")
+        .add_attribute("recipients", msgs.len().to_string())
+        .add_attribute("total_amount", total)
+        .add_messages(msgs);
+    Ok(res)
+}
+
+// execute_send, for a cw20 token
+pub fn execute_send_cw20(
+    deps: DepsMut,
+    env: Env,
+    token: String,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    // validate supplied addresses
+    let token = Cw20Contract(deps.api.addr_validate(&token)?);
+    let validated_address = deps.api.addr_validate(&recipient)?;
+
+    // check the contract has enough to execute the send
+    let balance = token.balance(&deps.querier, &env.contract.address)?;
+    if balance < amount {
+        return Err(ContractError::InsufficientContractBalance {});
+    }
+
+    let send_msg = token.call(Cw20ExecuteMsg::Transfer {
+        recipient: validated_address.to_string(),
+        amount,
+    })?;
+
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            cw20_amount: vec![Cw20CoinVerified {
+                address: token.addr(),
+                amount,
+            }],
+            recipient: Some(validated_address.to_string()),
+            ..ActionRecord::new(ActionKind::SendCw20, env.block.time)
+        },
+    )?;
+
+    let res = Response::new()
+        .add_attribute("message_type", "sudo")
+        .add_attribute("action", "send_cw20")
+        .add_attribute("token", token.addr())
+        .add_attribute("amount", amount)
+        .add_message(send_msg);
+    Ok(res)
+}
+
+// execute_burn, for a cw20 token
+pub fn execute_burn_cw20(
+    deps: DepsMut,
+    env: Env,
+    token: String,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    // validate supplied address
+    let token = Cw20Contract(deps.api.addr_validate(&token)?);
+
+    // either the amount asked for, or everything the contract holds
+    let balance = token.balance(&deps.querier, &env.contract.address)?;
+    let amount = amount.unwrap_or(balance);
+    if amount.is_zero() {
+        return Err(ContractError::InvalidAmount {});
+    }
+    if balance < amount {
+        return Err(ContractError::InsufficientContractBalance {});
+    }
+
+    let burn_msg = token.call(Cw20ExecuteMsg::Burn { amount })?;
+
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            cw20_amount: vec![Cw20CoinVerified {
+                address: token.addr(),
+                amount,
+            }],
+            ..ActionRecord::new(ActionKind::BurnCw20, env.block.time)
+        },
+    )?;
+
+    let res = Response::new()
+        .add_attribute("message_type", "sudo")
+        .add_attribute("action", "burn_cw20")
+        .add_attribute("token", token.addr())
+        .add_attribute("amount", amount)
+        .add_message(burn_msg);
+    Ok(res)
+}
+
+// includes a token in a full withdrawal and send all
+// a token that failed its transfers would block both,
+// so only governance can add one
+pub fn track_cw20(deps: DepsMut, env: Env, token: String) -> Result<Response, ContractError> {
+    // validate supplied address
+    let token = deps.api.addr_validate(&token)?;
+
+    if CW20_TOKENS.has(deps.storage, &token) {
+        return Err(ContractError::Cw20AlreadyTracked {});
+    }
+    Cw20Contract(token.clone())
+        .meta(&deps.querier)
+        .map_err(|_| ContractError::NotCw20Token {})?;
+    CW20_TOKENS.save(deps.storage, &token, &Empty {})?;
+
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            cw20_amount: vec![Cw20CoinVerified {
+                address: token.clone(),
+                amount: Uint128::zero(),
+            }],
+            ..ActionRecord::new(ActionKind::TrackCw20, env.block.time)
+        },
+    )?;
+
+    let res = Response::new()
+        .add_attribute("message_type", "sudo")
+        .add_attribute("action", "track_cw20")
+        .add_attribute("token", token);
+    Ok(res)
+}
+
+// stops a token being included in a full withdrawal or send all,
+// e.g. one that no longer answers balance queries
+// anything it holds can still be moved with execute_send_cw20
+pub fn untrack_cw20(deps: DepsMut, env: Env, token: String) -> Result<Response, ContractError> {
+    // validate supplied address
+    let token = deps.api.addr_validate(&token)?;
+
+    if !CW20_TOKENS.has(deps.storage, &token) {
+        return Err(ContractError::Cw20NotTracked {});
+    }
+    CW20_TOKENS.remove(deps.storage, &token);
+
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            cw20_amount: vec![Cw20CoinVerified {
+                address: token.clone(),
+                amount: Uint128::zero(),
+            }],
+            ..ActionRecord::new(ActionKind::UntrackCw20, env.block.time)
+        },
+    )?;
+
+    let res = Response::new()
+        .add_attribute("message_type", "sudo")
+        .add_attribute("action", "untrack_cw20")
+        .add_attribute("token", token);
+    Ok(res)
+}
+
+// execute_send, but to another chain
+// note that the recipient address can't be validated here
+pub fn execute_ibc_transfer(
+    deps: DepsMut,
+    env: Env,
+    channel_id: String,
+    to_address: String,
+    amount: Coin,
+    timeout_seconds: u64,
+) -> Result<Response, ContractError> {
+    if amount.amount.is_zero() {
+        return Err(ContractError::InvalidAmount {});
+    }
+    if timeout_seconds == 0 {
+        return Err(ContractError::InvalidIbcTimeout {});
+    }
+
+    // the transfer is refunded if not relayed in time
+    // checked, as a huge timeout would overflow the block time
+    let timeout = timeout_seconds
+        .checked_mul(1_000_000_000)
+        .and_then(|timeout_nanos| env.block.time.nanos().checked_add(timeout_nanos))
+        .map(Timestamp::from_nanos)
+        .ok_or(ContractError::InvalidIbcTimeout {})?;
+
+    // check the contract has enough to execute the transfer
+    ensure_denom_balance(deps.as_ref(), &env, &amount.denom, amount.amount)?;
+
+    add_to_total(deps.storage, std::slice::from_ref(&amount), |totals| {
+        &mut totals.sent
+    })?;
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            amount: vec![amount.clone()],
+            recipient: Some(to_address.clone()),
+            ..ActionRecord::new(ActionKind::IbcTransfer, env.block.time)
+        },
+    )?;
+
+    let res = Response::new()
+        .add_attribute("message_type", "sudo")
+        .add_attribute("action", "ibc_transfer")
+        .add_attribute("channel_id", &channel_id)
+        .add_attribute("to_address", &to_address)
+        .add_attribute("amount", amount.to_string())
+        .add_message(IbcMsg::Transfer {
+            channel_id,
+            to_address,
+            amount,
+            timeout: IbcTimeout::with_timestamp(timeout),
+        });
+    Ok(res)
+}
+
+// returns funds to the chain, rather than burning them
+pub fn execute_fund_community_pool(
+    deps: DepsMut,
+    env: Env,
+    amount: Option<Vec<Coin>>,
+) -> Result<Response, ContractError> {
+    // get contract balance
+    let contract_balances: Vec<Coin> = deps.querier.query_all_balances(&env.contract.address)?;
+
+    let amount = match amount {
+        // check the contract holds everything asked for
+        Some(amount) => {
+            validate_amount(&amount)?;
+            ensure_balances(&contract_balances, &amount)?;
+            amount
+        }
+        // otherwise, everything - as with send_all
+        None => {
+            // get config
+            let config = CONFIG.load(deps.storage)?;
+
+            // handle no native balance
+            if !contract_balances
+                .iter()
+                .any(|coin| coin.denom == config.native_denom)
+            {
+                return Err(ContractError::NoNativeBalance {});
+            }
+
+            contract_balances
+        }
+    };
+
+    add_to_total(deps.storage, &amount, |totals| {
+        &mut totals.sent_to_community_pool
+    })?;
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            amount: amount.clone(),
+            ..ActionRecord::new(ActionKind::FundCommunityPool, env.block.time)
+        },
+    )?;
+
+    let res = Response::new()
+        .add_attribute("message_type", "sudo")
+        .add_attribute("action", "fund_community_pool")
+        .add_attribute("amount", coins_to_string(&amount))
+        .add_message(DistributionMsg::FundCommunityPool { amount });
+    Ok(res)
+}
+
+// an explicit amount must actually ask for something,
+// and name each denom only once, as a bank send would
+fn validate_amount(amount: &[Coin]) -> Result<(), ContractError> {
+    if amount.is_empty() || amount.iter().any(|coin| coin.amount.is_zero()) {
+        return Err(ContractError::InvalidAmount {});
+    }
+    for (i, coin) in amount.iter().enumerate() {
+        if amount[..i].iter().any(|c| c.denom == coin.denom) {
+            return Err(ContractError::InvalidAmount {});
+        }
+    }
+    Ok(())
+}
+
+// checks the contract holds at least `amount`, denom by denom
+fn ensure_balances(contract_balances: &[Coin], amount: &[Coin]) -> Result<(), ContractError> {
+    for coin in amount {
+        let held = contract_balances
+            .iter()
+            .find(|&c| c.denom == coin.denom)
+            .map(|c| c.amount)
+            .unwrap_or_default();
+        if held < coin.amount {
+            return Err(ContractError::InsufficientContractBalance {});
+        }
+    }
+    Ok(())
+}
+
+// renders coins as e.g. 100ujuno,20uatom for attributes
+fn coins_to_string(coins: &[Coin]) -> String {
+    coins
+        .iter()
+        .map(|coin| coin.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+// puts locked native funds to work securing the chain
+pub fn execute_delegate(
+    deps: DepsMut,
+    env: Env,
+    validator: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    // the chain rejects staking nothing
+    if amount.is_zero() {
+        return Err(ContractError::InvalidAmount {});
+    }
+
+    // get config
+    let config = CONFIG.load(deps.storage)?;
+
+    // get native denom
+    let native_denom = config.native_denom;
+
+    // same balance checks as a send
+    ensure_native_balance(deps.as_ref(), &env, &native_denom, amount)?;
+
+    // check the validator exists
+    if deps.querier.query_validator(&validator)?.is_none() {
+        return Err(ContractError::ValidatorNotFound { validator });
+    }
+
+    let delegate_msg = StakingMsg::Delegate {
+        validator: validator.clone(),
+        amount: coin(amount.u128(), &native_denom),
+    };
+
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            amount: coins(amount.u128(), native_denom),
+            recipient: Some(validator.clone()),
+            ..ActionRecord::new(ActionKind::Delegate, env.block.time)
+        },
+    )?;
+
+    let res = Response::new()
+        .add_attribute("message_type", "sudo")
+        .add_attribute("action", "delegate")
+        .add_attribute("validator", validator)
+        .add_attribute("amount", amount)
+        .add_message(delegate_msg);
+    Ok(res)
+}
+
+// funds return to the contract once the unbonding period ends
+pub fn execute_undelegate(
+    deps: DepsMut,
+    env: Env,
+    validator: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    // the chain rejects staking nothing
+    if amount.is_zero() {
+        return Err(ContractError::InvalidAmount {});
+    }
+
+    // get config
+    let config = CONFIG.load(deps.storage)?;
+
+    // get native denom
+    let native_denom = config.native_denom;
+
+    // check there is enough delegated to this validator
+    let delegation = deps
+        .querier
+        .query_delegation(&env.contract.address, &validator)?
+        .ok_or(ContractError::InsufficientDelegation {})?;
+    if delegation.amount.amount < amount {
+        return Err(ContractError::InsufficientDelegation {});
+    }
+
+    let undelegate_msg = StakingMsg::Undelegate {
+        validator: validator.clone(),
+        amount: coin(amount.u128(), &native_denom),
+    };
+
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            amount: coins(amount.u128(), native_denom),
+            recipient: Some(validator.clone()),
+            ..ActionRecord::new(ActionKind::Undelegate, env.block.time)
+        },
+    )?;
+
+    let res = Response::new()
+        .add_attribute("message_type", "sudo")
+        .add_attribute("action", "undelegate")
+        .add_attribute("validator", validator)
+        .add_attribute("amount", amount)
+        .add_message(undelegate_msg);
+    Ok(res)
+}
+
+pub fn execute_redelegate(
+    deps: DepsMut,
+    env: Env,
+    src_validator: String,
+    dst_validator: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    // the chain rejects staking nothing
+    if amount.is_zero() {
+        return Err(ContractError::InvalidAmount {});
+    }
+
+    // get config
+    let config = CONFIG.load(deps.storage)?;
+
+    // get native denom
+    let native_denom = config.native_denom;
+
+    // check enough can be moved away from the source validator
+    let delegation = deps
+        .querier
+        .query_delegation(&env.contract.address, &src_validator)?
+        .ok_or(ContractError::InsufficientDelegation {})?;
+    if delegation.can_redelegate.amount < amount {
+        return Err(ContractError::InsufficientDelegation {});
+    }
+
+    // check the destination validator exists
+    if deps.querier.query_validator(&dst_validator)?.is_none() {
+        return Err(ContractError::ValidatorNotFound {
+            validator: dst_validator,
+        });
+    }
+
+    let redelegate_msg = StakingMsg::Redelegate {
+        src_validator: src_validator.clone(),
+        dst_validator: dst_validator.clone(),
+        amount: coin(amount.u128(), &native_denom),
+    };
+
+    record_action(
+        deps.storage,
+        &ActionRecord {
+            amount: coins(amount.u128(), native_denom),
+            recipient: Some(dst_validator.clone()),
+            ..ActionRecord::new(ActionKind::Redelegate, env.block.time)
+        },
+    )?;
+
+    let res = Response::new()
+        .add_attribute("message_type", "sudo")
+        .add_attribute("action", "redelegate")
+        .add_attribute("src_validator", src_validator)
+        .add_attribute("dst_validator", dst_validator)
+        .add_attribute("amount", amount)
+        .add_message(redelegate_msg);
+    Ok(res)
+}
+
+// governance can also retract a pending withdrawal
+pub fn sudo_cancel_withdraw(
+    deps: DepsMut,
+    env: Env,
+    beneficiary: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut res = Response::new()
+        .add_attribute("message_type", "sudo")
+        .add_attribute("action", "cancel_withdraw");
+
+    // the history records whose withdrawal was cancelled
+    let cancelled = match pending_beneficiaries(deps.as_ref(), beneficiary)? {
+        Some(beneficiaries) => {
+            let mut cancelled = vec![];
+            for (address, mut beneficiary) in beneficiaries {
+                beneficiary.withdrawal_ready = None;
+                beneficiary.veto = None;
+                BENEFICIARIES.save(deps.storage, &address, &beneficiary)?;
+                res = res.add_attribute("beneficiary", &address);
+                cancelled.push(address);
+            }
+            cancelled
+        }
+        None => {
+            clear_pending_withdrawal(deps.storage)?;
+            vec![CONFIG.load(deps.storage)?.withdraw_address]
+        }
+    };
+
+    for address in cancelled {
+        record_action(
+            deps.storage,
+            &ActionRecord {
+                recipient: Some(address.to_string()),
+                ..ActionRecord::new(ActionKind::CancelWithdraw, env.block.time)
+            },
+        )?;
+    }
+
+    Ok(res)
+}
+
+// unlike cancelling, this leaves the request in place
+// so that it is clear it was blocked, and why
+pub fn veto_withdrawal(
+    deps: DepsMut,
+    env: Env,
+    reason: String,
+    beneficiary: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut res = Response::new()
+        .add_attribute("message_type", "sudo")
+        .add_attribute("action", "veto_withdrawal")
+        .add_attribute("reason", &reason);
+
+    // the history records whose withdrawal was vetoed
+    let vetoed = match pending_beneficiaries(deps.as_ref(), beneficiary)? {
+        Some(beneficiaries) => {
+            let mut vetoed = vec![];
+            for (address, mut beneficiary) in beneficiaries {
+                beneficiary.veto = Some(reason.clone());
+                BENEFICIARIES.save(deps.storage, &address, &beneficiary)?;
+                res = res.add_attribute("beneficiary", &address);
+                vetoed.push(address);
+            }
+            vetoed
+        }
+        None => {
+            if WITHDRAWAL_READY.may_load(deps.storage)?.is_none() {
+                return Err(ContractError::NoPendingWithdrawal {});
+            }
+            WITHDRAWAL_VETO.save(deps.storage, &reason)?;
+            vec![CONFIG.load(deps.storage)?.withdraw_address]
+        }
+    };
+
+    for address in vetoed {
+        record_action(
+            deps.storage,
+            &ActionRecord {
+                recipient: Some(address.to_string()),
+                ..ActionRecord::new(ActionKind::VetoWithdrawal, env.block.time)
+            },
+        )?;
+    }
+
+    Ok(res)
+}
+
+// the beneficiaries a governance cancel or veto applies to,
+// either the one named or every one with a pending withdrawal
+// unset where there are no beneficiaries, and none was named
+fn pending_beneficiaries(
+    deps: Deps,
+    beneficiary: Option<String>,
+) -> Result<Option<Vec<(Addr, Beneficiary)>>, ContractError> {
+    let beneficiaries = match beneficiary {
+        Some(beneficiary) => {
+            let address = deps.api.addr_validate(&beneficiary)?;
+            let beneficiary = BENEFICIARIES
+                .may_load(deps.storage, &address)?
+                .ok_or(ContractError::UnknownBeneficiary {})?;
+            vec![(address, beneficiary)]
+        }
+        None if BENEFICIARIES.is_empty(deps.storage) => return Ok(None),
+        None => BENEFICIARIES
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?,
+    };
+
+    let pending: Vec<(Addr, Beneficiary)> = beneficiaries
+        .into_iter()
+        .filter(|(_, beneficiary)| beneficiary.withdrawal_ready.is_some())
+        .collect();
+    if pending.is_empty() {
+        return Err(ContractError::NoPendingWithdrawal {});
+    }
+
+    Ok(Some(pending))
+}
+
+// governance picks the guardians,
+// so a withdraw_address cannot approve its own withdrawal
+pub fn set_guardians(
+    deps: DepsMut,
+    env: Env,
+    guardians: Vec<String>,
+    threshold: u32,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    let mut members: Vec<Addr> = vec![];
+    for guardian in guardians {
+        let validated_address = deps.api.addr_validate(&guardian)?;
+        if members.contains(&validated_address) {
+            return Err(ContractError::InvalidGuardians {});
+        }
+        members.push(validated_address);
+    }
+
+    // an empty list, with a threshold of zero, removes the guardians
+    // vested and beneficiary claims have no single withdrawal to approve
+    let guardian_count = members.len();
+    let counts_claims = config.vesting.is_some() || !BENEFICIARIES.is_empty(deps.storage);
+    config.guardians = match (guardian_count, threshold) {
+        (0, 0) => None,
+        _ if counts_claims => return Err(ContractError::GuardiansUnsupported {}),
+        (_, 0) => return Err(ContractError::InvalidGuardians {}),
+        (count, threshold) if threshold as usize > count => {
+            return Err(ContractError::InvalidGuardians {})
+        }
+        _ => Some(GuardianSet { members, threshold }),
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    // approvals given under the old set do not count
+    WITHDRAWAL_APPROVALS.remove(deps.storage);
+
+    record_action(
+        deps.storage,
+        &ActionRecord::new(ActionKind::SetGuardians, env.block.time),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("message_type", "sudo")
+        .add_attribute("action", "set_guardians")
+        .add_attribute("guardians", guardian_count.to_string())
+        .add_attribute("threshold", threshold.to_string()))
+}
+
+// only governance can lift a freeze early
+// the council's cooldown still runs from when the freeze was due to end
+pub fn unfreeze(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    FROZEN_UNTIL.remove(deps.storage);
+
+    record_action(
+        deps.storage,
+        &ActionRecord::new(ActionKind::Unfreeze, env.block.time),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("message_type", "sudo")
+        .add_attribute("action", "unfreeze"))
+}
+
+// any freeze already in place is left to run its course
+pub fn set_emergency_council(
+    deps: DepsMut,
+    env: Env,
+    council: Option<EmergencyCouncilMsg>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    config.emergency_council = council
+        .map(|council| validate_emergency_council(deps.api, council))
+        .transpose()?;
+    CONFIG.save(deps.storage, &config)?;
+
+    record_action(
+        deps.storage,
+        &ActionRecord::new(ActionKind::SetEmergencyCouncil, env.block.time),
+    )?;
+
+    let mut res = Response::new()
+        .add_attribute("message_type", "sudo")
+        .add_attribute("action", "set_emergency_council");
+    if let Some(council) = config.emergency_council {
+        res = res
+            .add_attribute("emergency_council", council.address)
+            .add_attribute("freeze_duration", council.freeze_duration.to_string());
+    }
+    Ok(res)
+}
+
+// governance controls the funds
+// so it can also change who may withdraw them, and how
+pub fn update_config(
+    deps: DepsMut,
+    env: Env,
+    withdraw_address: Option<String>,
+    withdraw_delay: Option<Duration>,
+    native_denom: Option<String>,
+    rewards_destination: Option<RewardsDestination>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    let mut res = Response::new()
+        .add_attribute("message_type", "sudo")
+        .add_attribute("action", "update_config");
+
+    if let Some(withdraw_address) = withdraw_address {
+        // validate supplied address
+        let validated_address = deps.api.addr_validate(&withdraw_address)?;
+
+        // a new beneficiary must not inherit the old one's request,
+        // nor its nominee
+        if validated_address != config.withdraw_address {
+            reset_pending_withdrawal(deps.storage);
+            PENDING_WITHDRAW_ADDRESS.remove(deps.storage);
+        }
+
+        res = res
+            .add_attribute("old_withdraw_address", config.withdraw_address)
+            .add_attribute("new_withdraw_address", &validated_address);
+        config.withdraw_address = validated_address;
+    }
+
+    if let Some(withdraw_delay) = withdraw_delay {
+        // the claim window is not updated here,
+        // so the delay has to stay in the same unit
+        if let Some(window) = &config.withdraw_claim_window {
+            validate_claim_window(&withdraw_delay, window)?;
+        }
+
+        res = res
+            .add_attribute("old_withdraw_delay", config.withdraw_delay.to_string())
+            .add_attribute("new_withdraw_delay", withdraw_delay.to_string());
+        config.withdraw_delay = withdraw_delay;
+    }
+
+    if let Some(native_denom) = native_denom {
+        if native_denom.is_empty() {
+            return Err(ContractError::InvalidNativeDenom {});
+        }
+
+        // claims so far are counted in the old denom,
+        // so they cannot be set against a balance in the new one
+        let counts_claims = config.vesting.is_some() || !BENEFICIARIES.is_empty(deps.storage);
+        if native_denom != config.native_denom && counts_claims {
+            return Err(ContractError::NativeDenomLocked {});
+        }
+
+        res = res
+            .add_attribute("old_native_denom", config.native_denom)
+            .add_attribute("new_native_denom", &native_denom);
+        config.native_denom = native_denom;
+    }
+
+    if let Some(rewards_destination) = rewards_destination {
+        res = res
+            .add_attribute(
+                "old_rewards_destination",
+                config.rewards_destination.to_string(),
+            )
+            .add_attribute("new_rewards_destination", rewards_destination.to_string());
+        config.rewards_destination = rewards_destination;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    record_action(
+        deps.storage,
+        &ActionRecord::new(ActionKind::UpdateConfig, env.block.time),
+    )?;
+
+    Ok(res)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::GetConfig {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::GetWithdrawalReadyTime {} => to_json_binary(&get_withdraw_ready(deps)?),
+        QueryMsg::IsWithdrawalReady { beneficiary } => {
+            to_json_binary(&query_withdraw_ready(deps, env, beneficiary)?)
+        }
+        QueryMsg::GetPendingWithdrawAddress {} => {
+            to_json_binary(&query_pending_withdraw_address(deps)?)
+        }
+        QueryMsg::GetPendingRewards {} => to_json_binary(&query_pending_rewards(deps, env)?),
+        QueryMsg::GetCw20Balances {} => to_json_binary(&query_cw20_balances(deps, env)?),
+        QueryMsg::GetWithdrawalStatus { beneficiary } => {
+            to_json_binary(&query_withdrawal_status(deps, env, beneficiary)?)
+        }
+        QueryMsg::GetBalance {} => to_json_binary(&query_balance(deps, env)?),
+        QueryMsg::GetTotals {} => to_json_binary(&query_totals(deps)?),
+        QueryMsg::GetWithdrawalApprovals {} => to_json_binary(&query_withdrawal_approvals(deps)?),
+        QueryMsg::GetFreeze {} => to_json_binary(&query_freeze(deps, env)?),
+        QueryMsg::GetBeneficiaries {} => to_json_binary(&query_beneficiaries(deps, env)?),
+        QueryMsg::SimulateWithdraw { beneficiary } => {
+            to_json_binary(&query_simulate_withdraw(deps, env, beneficiary)?)
+        }
+        QueryMsg::SimulateSudo { msg } => {
+            to_json_binary(&simulate(deps, |deps| sudo(deps, env, msg)))
+        }
+        QueryMsg::GetActionHistory { start_after, limit } => {
+            to_json_binary(&query_action_history(deps, start_after, limit)?)
+        }
+    }
+}
+
+fn query_config(deps: Deps) -> StdResult<Config> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(config)
+}
+
+fn get_withdraw_ready(deps: Deps) -> StdResult<WithdrawalTimestampResponse> {
+    let withdrawal_ready_timestamp = WITHDRAWAL_READY.may_load(deps.storage)?;
+    match withdrawal_ready_timestamp {
+        Some(wra) => Ok(WithdrawalTimestampResponse {
+            withdrawal_ready_at: wra,
+        }),
+        None => Err(StdError::not_found(
+            "Withdrawal not yet requested - no Withdrawal time exists",
+        )),
+    }
+}
+
+fn query_withdraw_ready(
+    deps: Deps,
+    env: Env,
+    beneficiary: Option<String>,
+) -> StdResult<WithdrawalReadyResponse> {
+    let withdrawal_status = query_withdrawal_status(deps, env, beneficiary)?;
+
+    let is_withdrawal_ready = match withdrawal_status.status {
+        WithdrawalStatus::None => {
+            return Err(StdError::not_found(
+                "Withdrawal not yet requested - no Withdrawal time exists",
+            ))
+        }
+        // under vesting, a withdrawal is ready whenever something is claimable
+        WithdrawalStatus::Vesting {} => !withdrawal_status.amount.is_empty(),
+        WithdrawalStatus::Ready { .. } => true,
+        // a freeze or missing approvals hold even a ready withdrawal
+        _ => false,
+    };
+
+    Ok(WithdrawalReadyResponse {
+        is_withdrawal_ready,
+    })
+}
+
+fn query_withdrawal_status(
+    deps: Deps,
+    env: Env,
+    beneficiary: Option<String>,
+) -> StdResult<WithdrawalStatusResponse> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // a beneficiary runs its own timer, for its own share
+    if let Some((_, beneficiary)) = query_beneficiary(deps, beneficiary)? {
+        let status = withdrawal_status(
+            config.withdraw_claim_window,
+            beneficiary.withdrawal_ready,
+            beneficiary.veto.clone(),
+            &env,
+        )?;
+
+        // only a live withdrawal would send anything
+        let amount = match status {
+            WithdrawalStatus::Pending { .. } | WithdrawalStatus::Ready { .. } => {
+                let pool = beneficiary_pool(deps, &env, &config.native_denom)?;
+                let claimable = beneficiary_claimable(&beneficiary, &pool);
+                match claimable.is_zero() {
+                    true => vec![],
+                    false => coins(claimable.u128(), config.native_denom),
+                }
+            }
+            _ => vec![],
+        };
+
+        return Ok(WithdrawalStatusResponse {
+            status: frozen_status(deps, &env, status)?,
+            amount,
+            cw20_amount: vec![],
+        });
+    }
+
+    // under vesting there is no timer, just whatever has vested
+    if let Some(schedule) = config.vesting {
+        let claimable = vesting_claimable(deps, &env, &config.native_denom, &schedule)?;
+        let amount = match claimable.is_zero() {
+            true => vec![],
+            false => coins(claimable.u128(), config.native_denom),
+        };
+        return Ok(WithdrawalStatusResponse {
+            status: frozen_status(deps, &env, WithdrawalStatus::Vesting {})?,
+            amount,
+            cw20_amount: vec![],
+        });
+    }
+
+    let status = withdrawal_status(
+        config.withdraw_claim_window,
+        WITHDRAWAL_READY.may_load(deps.storage)?,
+        WITHDRAWAL_VETO.may_load(deps.storage)?,
+        &env,
+    )?;
+
+    // once the delay has passed, guardians may still have to sign off
+    let status = match status {
+        WithdrawalStatus::Ready { .. } => {
+            match approvals_outstanding(deps.storage, &config.guardians)? {
+                Some((approvals, threshold)) => WithdrawalStatus::AwaitingApproval {
+                    approvals,
+                    threshold,
+                },
+                None => status,
+            }
+        }
+        _ => status,
+    };
+
+    // only a live withdrawal would send anything
+    let (amount, cw20_amount) = match status {
+        WithdrawalStatus::Pending { .. }
+        | WithdrawalStatus::Ready { .. }
+        | WithdrawalStatus::AwaitingApproval { .. } => {
+            match WITHDRAWAL_AMOUNT.may_load(deps.storage)? {
+                Some(requested) => (requested, vec![]),
+                None => {
+                    let contract_balances =
+                        deps.querier.query_all_balances(&env.contract.address)?;
+                    let (cw20_amount, _) = cw20_transfers(deps, &env, &config.withdraw_address)?;
+                    (contract_balances, cw20_amount)
+                }
+            }
+        }
+        _ => (vec![], vec![]),
+    };
+
+    Ok(WithdrawalStatusResponse {
+        status: frozen_status(deps, &env, status)?,
+        amount,
+        cw20_amount,
+    })
+}
+
+// a freeze holds every kind of withdrawal, so it is reported first,
+// as long as there is a withdrawal to hold
+fn frozen_status(deps: Deps, env: &Env, status: WithdrawalStatus) -> StdResult<WithdrawalStatus> {
+    if status == WithdrawalStatus::None {
+        return Ok(status);
+    }
+
+    Ok(match frozen_until(deps.storage, env)? {
+        Some(until) => WithdrawalStatus::Frozen { until },
+        None => status,
+    })
+}
+
+// where a withdrawal on this timer is up to,
+// in the same order of checks as execute_withdraw
+fn withdrawal_status(
+    claim_window: Option<Duration>,
+    ready_at: Option<Expiration>,
+    veto: Option<String>,
+    env: &Env,
+) -> StdResult<WithdrawalStatus> {
+    let ready_at = match ready_at {
+        Some(ready_at) => ready_at,
+        None => return Ok(WithdrawalStatus::None),
+    };
+
+    let expires_at = withdrawal_expires_at(claim_window, ready_at)?;
+    let status = if let Some(reason) = veto {
+        WithdrawalStatus::Vetoed { reason }
+    } else if is_withdrawal_expired(claim_window, ready_at, env)? {
+        WithdrawalStatus::Expired {
+            // only reachable with a claim window
+            expired_at: expires_at.unwrap_or(ready_at),
+        }
+    } else if has_passed(&ready_at, env) {
+        WithdrawalStatus::Ready {
+            since: ready_at,
+            expires_at,
+        }
+    } else {
+        let remaining = match ready_at {
+            Expiration::AtHeight(height) => Duration::Height(height - env.block.height),
+            Expiration::AtTime(time) => Duration::Time(time.seconds() - env.block.time.seconds()),
+            // never stored, as the delay is always finite
+            Expiration::Never {} => Duration::Time(u64::MAX),
+        };
+        WithdrawalStatus::Pending {
+            ready_at,
+            remaining,
+        }
+    };
+
+    Ok(status)
+}
+
+// with beneficiaries, a query about a withdrawal has to say whose
+// unset where there are no beneficiaries, and none was named
+fn query_beneficiary(
+    deps: Deps,
+    beneficiary: Option<String>,
+) -> StdResult<Option<(Addr, Beneficiary)>> {
+    match beneficiary {
+        Some(beneficiary) => {
+            let address = deps.api.addr_validate(&beneficiary)?;
+            let beneficiary = BENEFICIARIES
+                .may_load(deps.storage, &address)?
+                .ok_or_else(|| StdError::not_found("Beneficiary"))?;
+            Ok(Some((address, beneficiary)))
+        }
+        None if BENEFICIARIES.is_empty(deps.storage) => Ok(None),
+        None => Err(StdError::generic_err(
+            "The contract has beneficiaries - a beneficiary must be given",
         )),
     }
 }
+
+fn query_pending_withdraw_address(deps: Deps) -> StdResult<PendingWithdrawAddressResponse> {
+    let pending_withdraw_address = PENDING_WITHDRAW_ADDRESS.may_load(deps.storage)?;
+    Ok(PendingWithdrawAddressResponse {
+        pending_withdraw_address,
+    })
+}
+
+fn query_pending_rewards(deps: Deps, env: Env) -> StdResult<PendingRewardsResponse> {
+    let pending_rewards = pending_rewards(deps, &env)?;
+    Ok(PendingRewardsResponse { pending_rewards })
+}
+
+fn query_cw20_balances(deps: Deps, env: Env) -> StdResult<Cw20BalancesResponse> {
+    let balances = cw20_balances(deps, &env)?;
+    Ok(Cw20BalancesResponse { balances })
+}
+
+fn query_balance(deps: Deps, env: Env) -> StdResult<ContractBalanceResponse> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // split the bank balances into native and everything else
+    let (native, other): (Vec<Coin>, Vec<Coin>) = deps
+        .querier
+        .query_all_balances(&env.contract.address)?
+        .into_iter()
+        .partition(|coin| coin.denom == config.native_denom);
+    let native = native
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| coin(0, &config.native_denom));
+
+    // delegations are always in the native denom
+    let delegations = deps.querier.query_all_delegations(&env.contract.address)?;
+    let staked = match delegations.is_empty() {
+        true => None,
+        false => {
+            let amount = delegations
+                .iter()
+                .filter(|delegation| delegation.amount.denom == config.native_denom)
+                .map(|delegation| delegation.amount.amount)
+                .sum::<Uint128>();
+            Some(coin(amount.u128(), &config.native_denom))
+        }
+    };
+
+    Ok(ContractBalanceResponse {
+        native,
+        other,
+        staked,
+    })
+}
+
+fn query_totals(deps: Deps) -> StdResult<Totals> {
+    let totals = TOTALS.may_load(deps.storage)?.unwrap_or_default();
+    Ok(totals)
+}
+
+fn query_action_history(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ActionHistoryResponse> {
+    let limit = limit
+        .unwrap_or(DEFAULT_HISTORY_LIMIT)
+        .min(MAX_HISTORY_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let actions = ACTION_HISTORY
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (id, action) = item?;
+            Ok(ActionHistoryEntry { id, action })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(ActionHistoryResponse { actions })
+}
+
+fn query_withdrawal_approvals(deps: Deps) -> StdResult<WithdrawalApprovalsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let approvals = WITHDRAWAL_APPROVALS
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+
+    Ok(WithdrawalApprovalsResponse {
+        approvals,
+        threshold: config.guardians.map(|guardians| guardians.threshold),
+    })
+}
+
+fn query_freeze(deps: Deps, env: Env) -> StdResult<FreezeResponse> {
+    let frozen_until = frozen_until(deps.storage, &env)?;
+    Ok(FreezeResponse { frozen_until })
+}
+
+fn query_beneficiaries(deps: Deps, env: Env) -> StdResult<BeneficiariesResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let pool = beneficiary_pool(deps, &env, &config.native_denom)?;
+
+    let beneficiaries = BENEFICIARIES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (address, beneficiary) = item?;
+            Ok(BeneficiaryInfo {
+                claimable: beneficiary_claimable(&beneficiary, &pool),
+                address,
+                shares: beneficiary.shares,
+                claimed: beneficiary.claimed,
+                withdrawal_ready: beneficiary.withdrawal_ready,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(BeneficiariesResponse { beneficiaries })
+}
+
+fn query_simulate_withdraw(
+    deps: Deps,
+    env: Env,
+    beneficiary: Option<String>,
+) -> StdResult<SimulationResponse> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // run as the beneficiary, if the contract has them
+    let sender = match query_beneficiary(deps, beneficiary)? {
+        Some((address, _)) => address,
+        None => config.withdraw_address,
+    };
+    let info = MessageInfo {
+        sender,
+        funds: vec![],
+    };
+
+    Ok(simulate(deps, |deps| execute_withdraw(deps, env, info)))
+}
+
+// runs a handler against throwaway storage,
+// so it sees current state and balances but changes nothing
+fn simulate<F>(deps: Deps, handler: F) -> SimulationResponse
+where
+    F: FnOnce(DepsMut) -> Result<Response, ContractError>,
+{
+    let mut storage = SimulatedStorage::new(deps.storage);
+    let deps_mut = DepsMut {
+        storage: &mut storage,
+        api: deps.api,
+        querier: deps.querier,
+    };
+
+    match handler(deps_mut) {
+        Ok(res) => SimulationResponse {
+            messages: res
+                .messages
+                .into_iter()
+                .map(|sub_msg| sub_msg.msg)
+                .collect(),
+            error: None,
+        },
+        Err(err) => SimulationResponse {
+            messages: vec![],
+            error: Some(err.to_string()),
+        },
+    }
+}
@@ -1,4 +1,5 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
+use cw_utils::Expiration;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -6,18 +7,127 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("Semver parsing error: {0}")]
+    SemVer(String),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
     #[error("Withdrawal not ready - wait until after timeout has passed")]
     WithdrawalNotReady {},
 
+    #[error("Withdrawal expired - the claim window has passed, start a new withdrawal")]
+    WithdrawalExpired {},
+
+    #[error("Claim window must be non-zero, and in the same unit as the withdraw delay")]
+    InvalidClaimWindow {},
+
+    #[error("Withdrawal vetoed by governance: {reason}")]
+    WithdrawalVetoed { reason: String },
+
+    #[error("There is no pending withdrawal")]
+    NoPendingWithdrawal {},
+
+    #[error("Guardians must be unique, with a threshold between one and the number of guardians")]
+    InvalidGuardians {},
+
+    #[error("Guardians cannot be set alongside a vesting schedule or beneficiaries")]
+    GuardiansUnsupported {},
+
+    #[error("This guardian has already approved the pending withdrawal")]
+    AlreadyApproved {},
+
+    #[error("Withdrawal needs {threshold} guardian approvals, but has {approvals}")]
+    InsufficientApprovals { approvals: u32, threshold: u32 },
+
+    #[error("Withdrawals are frozen by the emergency council")]
+    Frozen {},
+
+    #[error("Withdrawals are already frozen - a freeze cannot be extended")]
+    AlreadyFrozen {},
+
+    #[error("The emergency council cannot freeze again until {until}")]
+    FreezeCooldown { until: Expiration },
+
+    #[error("Freeze duration must be non-zero")]
+    InvalidFreezeDuration {},
+
     #[error("Contract balance is too small to execute")]
     InsufficientContractBalance {},
 
+    #[error("Contract balance of {denom} is {shortfall} short of the amount requested")]
+    InsufficientDenomBalance { denom: String, shortfall: Uint128 },
+
     #[error("A native balance was not found in the Contract balances")]
     NoNativeBalance {},
 
+    #[error("Native denom must be non-empty")]
+    InvalidNativeDenom {},
+
+    #[error("Native denom cannot change while vesting or beneficiaries count their claims in it")]
+    NativeDenomLocked {},
+
+    #[error("Vesting schedule is invalid - expected start <= cliff <= end")]
+    InvalidVestingSchedule {},
+
+    #[error("Withdrawals follow the vesting schedule - there is no withdrawal to start")]
+    VestingScheduleActive {},
+
+    #[error("Nothing has vested that has not already been claimed")]
+    NothingVested {},
+
+    #[error(
+        "Beneficiaries must be unique, with non-zero shares, and cannot be combined with vesting"
+    )]
+    InvalidBeneficiaries {},
+
+    #[error("Not a beneficiary of this contract")]
+    UnknownBeneficiary {},
+
+    #[error("A beneficiary always withdraws its whole share - amount must be unset")]
+    BeneficiaryPartialWithdrawal {},
+
+    #[error("Validator not found: {validator}")]
+    ValidatorNotFound { validator: String },
+
+    #[error("The contract has no delegations to claim rewards from")]
+    NoDelegations {},
+
+    #[error("Delegation is too small to execute")]
+    InsufficientDelegation {},
+
+    #[error("Amount must be a non-empty list of non-zero coins, one per denom")]
+    InvalidAmount {},
+
+    #[error("Not a cw20 token contract")]
+    NotCw20Token {},
+
+    #[error("This cw20 token is already tracked by the contract")]
+    Cw20AlreadyTracked {},
+
+    #[error("This cw20 token is not tracked by the contract")]
+    Cw20NotTracked {},
+
+    #[error("IBC transfer timeout must be at least one second, and fit within a block time")]
+    InvalidIbcTimeout {},
+
+    #[error("Cannot migrate from a different contract: {previous_contract}")]
+    CannotMigrate { previous_contract: String },
+
+    #[error(
+        "Cannot migrate from a newer version ({previous_version}) to an older one ({new_version})"
+    )]
+    CannotMigrateVersion {
+        previous_version: String,
+        new_version: String,
+    },
+
     #[error("Custom Error val: {val:?}")]
     CustomError { val: String },
 }
+
+impl From<semver::Error> for ContractError {
+    fn from(err: semver::Error) -> Self {
+        Self::SemVer(err.to_string())
+    }
+}
@@ -1,10 +1,101 @@
 #[cfg(test)]
 mod tests {
     use crate::helpers::CwTemplateContract;
-    use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, SudoMsg, WithdrawalReadyResponse};
+    use crate::msg::{
+        ActionHistoryResponse, EmergencyCouncilMsg, ExecuteMsg, InstantiateMsg, QueryMsg, SudoMsg,
+        WithdrawalReadyResponse,
+    };
+    use crate::state::{ActionKind, ActionRecord, Totals, VestingSchedule};
+
+    use cosmwasm_std::testing::{MockApi, MockStorage};
+    use cosmwasm_std::{
+        coin, coins, Addr, Api, BankMsg, Binary, BlockInfo, Coin, CustomQuery, DistributionMsg,
+        Empty, Querier, Storage, Uint128,
+    };
+    use cw_multi_test::{
+        AppBuilder, AppResponse, BankKeeper, Contract, ContractWrapper, CosmosRouter, Distribution,
+        DistributionKeeper, Executor, FailingModule, IbcAcceptingModule, Module, StakeKeeper,
+        WasmKeeper,
+    };
+    use cw_utils::{Expiration, DAY};
+    use schemars::JsonSchema;
+    use serde::de::DeserializeOwned;
+
+    // multi-test's distribution module has no community pool,
+    // so this one treats funding it as a send to a well-known address
+    const COMMUNITY_POOL: &str = "community-pool";
+
+    struct CommunityPoolDistribution(DistributionKeeper);
+
+    impl Module for CommunityPoolDistribution {
+        type ExecT = DistributionMsg;
+        type QueryT = Empty;
+        type SudoT = Empty;
+
+        fn execute<ExecC, QueryC>(
+            &self,
+            api: &dyn Api,
+            storage: &mut dyn Storage,
+            router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+            block: &BlockInfo,
+            sender: Addr,
+            msg: DistributionMsg,
+        ) -> anyhow::Result<AppResponse>
+        where
+            ExecC: std::fmt::Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
+            QueryC: CustomQuery + DeserializeOwned + 'static,
+        {
+            match msg {
+                DistributionMsg::FundCommunityPool { amount } => {
+                    let send = BankMsg::Send {
+                        to_address: COMMUNITY_POOL.to_string(),
+                        amount,
+                    };
+                    router.execute(api, storage, block, sender, send.into())
+                }
+                msg => self.0.execute(api, storage, router, block, sender, msg),
+            }
+        }
+
+        fn sudo<ExecC, QueryC>(
+            &self,
+            api: &dyn Api,
+            storage: &mut dyn Storage,
+            router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+            block: &BlockInfo,
+            msg: Empty,
+        ) -> anyhow::Result<AppResponse>
+        where
+            ExecC: std::fmt::Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
+            QueryC: CustomQuery + DeserializeOwned + 'static,
+        {
+            self.0.sudo(api, storage, router, block, msg)
+        }
+
+        fn query(
+            &self,
+            api: &dyn Api,
+            storage: &dyn Storage,
+            querier: &dyn Querier,
+            block: &BlockInfo,
+            request: Empty,
+        ) -> anyhow::Result<Binary> {
+            self.0.query(api, storage, querier, block, request)
+        }
+    }
+
+    impl Distribution for CommunityPoolDistribution {}
 
-    use cosmwasm_std::{coins, Addr, BlockInfo, Coin, Empty, Uint128};
-    use cw_multi_test::{App, AppBuilder, AppResponse, Contract, ContractWrapper, Executor};
+    type App = cw_multi_test::App<
+        BankKeeper,
+        MockApi,
+        MockStorage,
+        FailingModule<Empty, Empty, Empty>,
+        WasmKeeper<Empty, Empty>,
+        StakeKeeper,
+        CommunityPoolDistribution,
+        IbcAcceptingModule,
+    >;
 
     pub fn contract_template() -> Box<dyn Contract<Empty>> {
         let contract = ContractWrapper::new(
@@ -12,7 +103,9 @@ mod tests {
             crate::contract::instantiate,
             crate::contract::query,
         );
-        let contract_with_sudo = contract.with_sudo(crate::contract::sudo);
+        let contract_with_sudo = contract
+            .with_sudo(crate::contract::sudo)
+            .with_migrate(crate::contract::migrate);
         Box::new(contract_with_sudo)
     }
 
@@ -21,19 +114,23 @@ mod tests {
     const NATIVE_DENOM: &str = "ujuno";
 
     fn mock_app() -> App {
-        AppBuilder::new().build(|router, _, storage| {
-            router
-                .bank
-                .init_balance(
-                    storage,
-                    &Addr::unchecked(USER),
-                    vec![Coin {
-                        denom: NATIVE_DENOM.to_string(),
-                        amount: Uint128::new(3_000_000),
-                    }],
-                )
-                .unwrap();
-        })
+        let distribution = CommunityPoolDistribution(DistributionKeeper::new());
+        AppBuilder::new()
+            .with_distribution(distribution)
+            .with_ibc(IbcAcceptingModule)
+            .build(|router, _, storage| {
+                router
+                    .bank
+                    .init_balance(
+                        storage,
+                        &Addr::unchecked(USER),
+                        vec![Coin {
+                            denom: NATIVE_DENOM.to_string(),
+                            amount: Uint128::new(3_000_000),
+                        }],
+                    )
+                    .unwrap();
+            })
     }
 
     fn mock_instantiate(days: u64) -> (App, CwTemplateContract, Addr) {
@@ -42,12 +139,17 @@ mod tests {
 
         let withdraw_address = String::from("gordon-gekko-address"); // in reality this would be e.g. juno16g2rahf5846rxzp3fwlswy08fz8ccuwk03k57y
         let _validated_addr = Addr::unchecked(&withdraw_address);
-        let withdraw_delay_in_days = days; // this is what we are expecting to set it to
+        let withdraw_delay = DAY * days; // this is what we are expecting to set it to
 
         let msg = InstantiateMsg {
             withdraw_address,
-            withdraw_delay_in_days,
+            withdraw_delay,
+            withdraw_claim_window: None,
             native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
         };
 
         let cw_template_contract_addr = app
@@ -75,12 +177,17 @@ mod tests {
 
         let withdraw_address = String::from("gordon-gekko-address"); // in reality this would be e.g. juno16g2rahf5846rxzp3fwlswy08fz8ccuwk03k57y
         let _validated_addr = Addr::unchecked(&withdraw_address);
-        let withdraw_delay_in_days = 28; // this is what we are expecting to set it to
+        let withdraw_delay = DAY * 28; // this is what we are expecting to set it to
 
         let msg = InstantiateMsg {
             withdraw_address,
-            withdraw_delay_in_days,
+            withdraw_delay,
+            withdraw_claim_window: None,
             native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
         };
 
         let cw_template_contract_addr = app
@@ -99,6 +206,46 @@ mod tests {
         (app, cw_template_contract, cw_template_contract_addr)
     }
 
+    fn mock_instantiate_vesting(days: u64) -> (App, CwTemplateContract, Addr) {
+        let mut app = mock_app();
+        let cw_template_id = app.store_code(contract_template());
+
+        // vest linearly from now, with a cliff halfway through
+        let now = app.block_info().time;
+        let seconds = days * 86400;
+        let vesting = VestingSchedule {
+            start_time: now,
+            cliff_time: now.plus_seconds(seconds / 2),
+            end_time: now.plus_seconds(seconds),
+        };
+
+        let msg = InstantiateMsg {
+            withdraw_address: String::from("gordon-gekko-address"),
+            withdraw_delay: DAY * days,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: Some(vesting),
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let cw_template_contract_addr = app
+            .instantiate_contract(
+                cw_template_id,
+                Addr::unchecked(USER),
+                &msg,
+                &coins(3_000_000, NATIVE_DENOM),
+                "cw-unity-prop",
+                None,
+            )
+            .unwrap();
+
+        let cw_template_contract = CwTemplateContract(cw_template_contract_addr.clone());
+
+        (app, cw_template_contract, cw_template_contract_addr)
+    }
+
     fn advance_one_day_one_hour(block: &mut BlockInfo) {
         let one_day_one_hour_in_seconds = 90_000;
         block.time = block.time.plus_seconds(one_day_one_hour_in_seconds);
@@ -108,7 +255,7 @@ mod tests {
     }
 
     fn is_withdrawal_ready(app: &mut App, contract_address: Addr) -> WithdrawalReadyResponse {
-        let msg = QueryMsg::IsWithdrawalReady {};
+        let msg = QueryMsg::IsWithdrawalReady { beneficiary: None };
         let result: WithdrawalReadyResponse =
             app.wrap().query_wasm_smart(contract_address, &msg).unwrap();
         result
@@ -124,7 +271,7 @@ mod tests {
             let withdraw_address = String::from("gordon-gekko-address");
             let validated_addr = Addr::unchecked(&withdraw_address);
 
-            let msg = ExecuteMsg::StartWithdraw {};
+            let msg = ExecuteMsg::StartWithdraw { amount: None };
             let cosmos_msg = cw_template_contract.call(msg).unwrap();
             app.execute(validated_addr, cosmos_msg).unwrap();
 
@@ -145,7 +292,7 @@ mod tests {
             let withdraw_address = String::from("gordon-gekko-address");
             let validated_addr = Addr::unchecked(&withdraw_address);
 
-            let msg = ExecuteMsg::StartWithdraw {};
+            let msg = ExecuteMsg::StartWithdraw { amount: None };
             let cosmos_msg = cw_template_contract.call(msg).unwrap();
             app.execute(validated_addr.clone(), cosmos_msg).unwrap();
 
@@ -185,6 +332,36 @@ mod tests {
             assert_eq!(withdrawer_balance, coins(3_000_000, NATIVE_DENOM));
         }
 
+        #[test]
+        fn start_partial_withdraw_then_claim() {
+            let (mut app, cw_template_contract, contract_addr) = mock_instantiate(1);
+
+            let withdraw_address = String::from("gordon-gekko-address");
+            let validated_addr = Addr::unchecked(&withdraw_address);
+
+            let msg = ExecuteMsg::StartWithdraw {
+                amount: Some(coins(1_000_000, NATIVE_DENOM)),
+            };
+            let cosmos_msg = cw_template_contract.call(msg).unwrap();
+            app.execute(validated_addr.clone(), cosmos_msg).unwrap();
+
+            // move time forward
+            app.update_block(advance_one_day_one_hour);
+
+            //now claim
+            let claim_msg = ExecuteMsg::ExecuteWithdraw {};
+            let claim_msg_res = cw_template_contract.call(claim_msg).unwrap();
+            app.execute(validated_addr.clone(), claim_msg_res).unwrap();
+
+            // contract keeps the rest
+            let contract_balance = get_balance(&mut app, &contract_addr);
+            assert_eq!(contract_balance, coins(2_000_000, NATIVE_DENOM));
+
+            // withdrawer only has what they asked for
+            let withdrawer_balance = get_balance(&mut app, &validated_addr);
+            assert_eq!(withdrawer_balance, coins(1_000_000, NATIVE_DENOM));
+        }
+
         #[test]
         fn start_claim_no_withdraw() {
             let (mut app, cw_template_contract, _) = mock_instantiate(1);
@@ -204,7 +381,7 @@ mod tests {
             let withdraw_address = String::from("gordon-gekko-address");
             let validated_addr = Addr::unchecked(&withdraw_address);
 
-            let msg = ExecuteMsg::StartWithdraw {};
+            let msg = ExecuteMsg::StartWithdraw { amount: None };
             let cosmos_msg = cw_template_contract.call(msg).unwrap();
             app.execute(validated_addr, cosmos_msg).unwrap();
 
@@ -218,7 +395,7 @@ mod tests {
             );
 
             // community decides to burn
-            exec_sudo_burn(&mut app, contract_addr.clone()).unwrap();
+            exec_sudo_burn(&mut app, contract_addr.clone(), None).unwrap();
 
             let contract_balance = get_balance(&mut app, &contract_addr);
 
@@ -233,7 +410,7 @@ mod tests {
             let withdraw_address = String::from("gordon-gekko-address");
             let validated_addr = Addr::unchecked(&withdraw_address);
 
-            let msg = ExecuteMsg::StartWithdraw {};
+            let msg = ExecuteMsg::StartWithdraw { amount: None };
             let cosmos_msg = cw_template_contract.call(msg).unwrap();
             app.execute(validated_addr.clone(), cosmos_msg).unwrap();
 
@@ -260,7 +437,7 @@ mod tests {
             );
 
             // community decides to burn
-            exec_sudo_burn(&mut app, contract_addr.clone()).unwrap();
+            exec_sudo_burn(&mut app, contract_addr.clone(), None).unwrap();
 
             let contract_balance = get_balance(&mut app, &contract_addr);
 
@@ -281,7 +458,7 @@ mod tests {
             let withdraw_address = String::from("gordon-gekko-address");
             let validated_addr = Addr::unchecked(&withdraw_address);
 
-            let msg = ExecuteMsg::StartWithdraw {};
+            let msg = ExecuteMsg::StartWithdraw { amount: None };
             let cosmos_msg = cw_template_contract.call(msg).unwrap();
             app.execute(validated_addr, cosmos_msg).unwrap();
 
@@ -318,11 +495,168 @@ mod tests {
             assert_eq!(contract_balance, coins(500_000, NATIVE_DENOM));
         }
 
+        #[test]
+        fn vesting_claim_in_tranches() {
+            let (mut app, cw_template_contract, contract_addr) = mock_instantiate_vesting(4);
+
+            let withdraw_address = String::from("gordon-gekko-address");
+            let validated_addr = Addr::unchecked(&withdraw_address);
+
+            // before the cliff nothing has vested
+            app.update_block(advance_one_day_one_hour);
+            let claim_msg = cw_template_contract
+                .call(ExecuteMsg::ExecuteWithdraw {})
+                .unwrap();
+            app.execute(validated_addr.clone(), claim_msg.clone())
+                .unwrap_err();
+
+            // past the cliff, part of the balance can be claimed
+            app.update_block(advance_one_day_one_hour);
+            app.execute(validated_addr.clone(), claim_msg.clone())
+                .unwrap();
+
+            let withdrawer_balance = get_balance(&mut app, &validated_addr);
+            assert_ne!(withdrawer_balance, &[]);
+            assert_ne!(withdrawer_balance, coins(3_000_000, NATIVE_DENOM));
+
+            // once fully vested, the rest follows
+            app.update_block(advance_one_day_one_hour);
+            app.update_block(advance_one_day_one_hour);
+            app.execute(validated_addr.clone(), claim_msg).unwrap();
+
+            let contract_balance = get_balance(&mut app, &contract_addr);
+            assert_eq!(contract_balance, &[]);
+
+            let withdrawer_balance = get_balance(&mut app, &validated_addr);
+            assert_eq!(withdrawer_balance, coins(3_000_000, NATIVE_DENOM));
+        }
+
+        #[test]
+        fn start_withdraw_then_sudo_cancel_then_claim() {
+            let (mut app, cw_template_contract, contract_addr) = mock_instantiate(1);
+
+            let withdraw_address = String::from("gordon-gekko-address");
+            let validated_addr = Addr::unchecked(&withdraw_address);
+
+            let msg = ExecuteMsg::StartWithdraw { amount: None };
+            let cosmos_msg = cw_template_contract.call(msg).unwrap();
+            app.execute(validated_addr.clone(), cosmos_msg).unwrap();
+
+            // community retracts the withdrawal
+            app.wasm_sudo(
+                contract_addr.clone(),
+                &SudoMsg::CancelWithdraw { beneficiary: None },
+            )
+            .unwrap();
+
+            // move time forward
+            app.update_block(advance_one_day_one_hour);
+
+            //now claim
+            // this will error
+            let claim_msg = ExecuteMsg::ExecuteWithdraw {};
+            let claim_msg_res = cw_template_contract.call(claim_msg).unwrap();
+            app.execute(validated_addr, claim_msg_res).unwrap_err();
+
+            // funds are untouched
+            let contract_balance = get_balance(&mut app, &contract_addr);
+            assert_eq!(contract_balance, coins(3_000_000, NATIVE_DENOM));
+        }
+
+        #[test]
+        fn start_withdraw_then_sudo_veto_then_claim() {
+            let (mut app, cw_template_contract, contract_addr) = mock_instantiate(1);
+
+            let withdraw_address = String::from("gordon-gekko-address");
+            let validated_addr = Addr::unchecked(&withdraw_address);
+
+            let msg = ExecuteMsg::StartWithdraw { amount: None };
+            let cosmos_msg = cw_template_contract.call(msg).unwrap();
+            app.execute(validated_addr.clone(), cosmos_msg).unwrap();
+
+            // community vetoes the withdrawal
+            let veto_msg = SudoMsg::VetoWithdrawal {
+                reason: String::from("greed is not good"),
+                beneficiary: None,
+            };
+            app.wasm_sudo(contract_addr.clone(), &veto_msg).unwrap();
+
+            // move time forward
+            app.update_block(advance_one_day_one_hour);
+
+            // not ready, despite the delay having passed
+            let withdrawal_ready = is_withdrawal_ready(&mut app, contract_addr.clone());
+            assert_eq!(
+                withdrawal_ready,
+                WithdrawalReadyResponse {
+                    is_withdrawal_ready: false,
+                }
+            );
+
+            //now claim
+            // this will error
+            let claim_msg = ExecuteMsg::ExecuteWithdraw {};
+            let claim_msg_res = cw_template_contract.call(claim_msg).unwrap();
+            app.execute(validated_addr, claim_msg_res).unwrap_err();
+
+            // funds are untouched
+            let contract_balance = get_balance(&mut app, &contract_addr);
+            assert_eq!(contract_balance, coins(3_000_000, NATIVE_DENOM));
+        }
+
+        #[test]
+        fn handover_then_withdraw() {
+            let (mut app, cw_template_contract, contract_addr) = mock_instantiate(1);
+
+            let withdraw_address = String::from("gordon-gekko-address");
+            let validated_addr = Addr::unchecked(&withdraw_address);
+            let multisig_addr = Addr::unchecked("blue-star-multisig");
+
+            // old address starts a withdrawal, then hands over
+            let msg = ExecuteMsg::StartWithdraw { amount: None };
+            let cosmos_msg = cw_template_contract.call(msg).unwrap();
+            app.execute(validated_addr.clone(), cosmos_msg).unwrap();
+
+            let msg = ExecuteMsg::ProposeNewWithdrawAddress {
+                address: multisig_addr.to_string(),
+            };
+            let cosmos_msg = cw_template_contract.call(msg).unwrap();
+            app.execute(validated_addr, cosmos_msg).unwrap();
+
+            let msg = ExecuteMsg::AcceptWithdrawAddress {};
+            let cosmos_msg = cw_template_contract.call(msg).unwrap();
+            app.execute(multisig_addr.clone(), cosmos_msg).unwrap();
+
+            // move time forward
+            app.update_block(advance_one_day_one_hour);
+
+            // the old request did not survive the handover
+            let claim_msg = ExecuteMsg::ExecuteWithdraw {};
+            let claim_msg_res = cw_template_contract.call(claim_msg).unwrap();
+            app.execute(multisig_addr.clone(), claim_msg_res.clone())
+                .unwrap_err();
+
+            // so the new address waits out its own delay
+            let msg = ExecuteMsg::StartWithdraw { amount: None };
+            let cosmos_msg = cw_template_contract.call(msg).unwrap();
+            app.execute(multisig_addr.clone(), cosmos_msg).unwrap();
+
+            app.update_block(advance_one_day_one_hour);
+
+            app.execute(multisig_addr.clone(), claim_msg_res).unwrap();
+
+            let contract_balance = get_balance(&mut app, &contract_addr);
+            assert_eq!(contract_balance, &[]);
+
+            let multisig_balance = get_balance(&mut app, &multisig_addr);
+            assert_eq!(multisig_balance, coins(3_000_000, NATIVE_DENOM));
+        }
+
         #[test]
         fn start_withdraw_fails_with_wrong_address() {
             let (mut app, cw_template_contract, _contract_addr) = mock_instantiate(28);
 
-            let msg = ExecuteMsg::StartWithdraw {};
+            let msg = ExecuteMsg::StartWithdraw { amount: None };
             let cosmos_msg = cw_template_contract.call(msg).unwrap();
 
             // we expect this to fail
@@ -330,10 +664,52 @@ mod tests {
             app.execute(Addr::unchecked("some-random-address"), cosmos_msg)
                 .unwrap_err();
         }
+
+        #[test]
+        fn start_withdraw_then_claim_is_recorded() {
+            let (mut app, cw_template_contract, contract_addr) = mock_instantiate(1);
+
+            let validated_addr = Addr::unchecked("gordon-gekko-address");
+
+            let msg = ExecuteMsg::StartWithdraw { amount: None };
+            let cosmos_msg = cw_template_contract.call(msg).unwrap();
+            app.execute(validated_addr.clone(), cosmos_msg).unwrap();
+
+            // move time forward
+            app.update_block(advance_one_day_one_hour);
+
+            let claim_msg = ExecuteMsg::ExecuteWithdraw {};
+            let claim_msg_res = cw_template_contract.call(claim_msg).unwrap();
+            app.execute(validated_addr.clone(), claim_msg_res).unwrap();
+
+            let msg = QueryMsg::GetActionHistory {
+                start_after: None,
+                limit: None,
+            };
+            let res: ActionHistoryResponse =
+                app.wrap().query_wasm_smart(contract_addr, &msg).unwrap();
+
+            // the claim is the last entry
+            let claim = res.actions.last().unwrap();
+            assert_eq!(claim.id, 1);
+            assert_eq!(
+                claim.action,
+                ActionRecord {
+                    actor: Some(validated_addr.clone()),
+                    amount: coins(3_000_000, NATIVE_DENOM),
+                    recipient: Some(validated_addr.to_string()),
+                    ..ActionRecord::new(ActionKind::ExecuteWithdraw, app.block_info().time)
+                }
+            );
+        }
     }
 
-    fn exec_sudo_burn(app: &mut App, contract_address: Addr) -> anyhow::Result<AppResponse> {
-        let msg = SudoMsg::ExecuteBurn {};
+    fn exec_sudo_burn(
+        app: &mut App,
+        contract_address: Addr,
+        amount: Option<Vec<Coin>>,
+    ) -> anyhow::Result<AppResponse> {
+        let msg = SudoMsg::ExecuteBurn { amount };
         app.wasm_sudo(contract_address, &msg)
     }
 
@@ -343,7 +719,11 @@ mod tests {
         recipient: String,
         amount: Uint128,
     ) -> anyhow::Result<AppResponse> {
-        let msg = SudoMsg::ExecuteSend { recipient, amount };
+        let msg = SudoMsg::ExecuteSend {
+            recipient,
+            amount,
+            denom: None,
+        };
         app.wasm_sudo(contract_address, &msg)
     }
 
@@ -356,74 +736,438 @@ mod tests {
         app.wasm_sudo(contract_address, &msg)
     }
 
+    fn exec_sudo_fund_community_pool(
+        app: &mut App,
+        contract_address: Addr,
+        amount: Option<Vec<Coin>>,
+    ) -> anyhow::Result<AppResponse> {
+        let msg = SudoMsg::FundCommunityPool { amount };
+        app.wasm_sudo(contract_address, &msg)
+    }
+
     fn get_balance(app: &mut App, address: &Addr) -> Vec<Coin> {
         app.wrap().query_all_balances(address).unwrap()
     }
 
-    mod sudo {
+    mod migrate {
         use super::*;
 
-        #[test]
-        fn sudo_burn() {
-            let (mut app, _cw_template_contract, contract_addr) = mock_instantiate(28);
+        use crate::msg::{MigrateMsg, WithdrawalTimestampResponse};
+        use crate::state::{
+            Config, ConfigV0_3, ConfigV0_4, RewardsDestination, CONFIG_V0_3, CONFIG_V0_4,
+            WITHDRAWAL_READY_V0_4,
+        };
+        use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, StdResult};
+        use cw2::{set_contract_version, ContractVersion};
+        use cw_storage_plus::Item;
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+        struct LegacyInstantiateMsg {
+            contract_version: String,
+            rewards_destination: Option<RewardsDestination>,
+        }
 
-            // this tests for success
-            exec_sudo_burn(&mut app, contract_addr.clone()).unwrap();
+        // config as written by v0.4.0 builds from before rewards_destination
+        #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+        struct EarlyConfigV0_4 {
+            withdraw_address: Addr,
+            withdraw_delay_in_days: u64,
+            withdraw_claim_window_in_days: Option<u64>,
+            native_denom: String,
+            vesting: Option<VestingSchedule>,
+        }
 
-            let contract_balance = get_balance(&mut app, &contract_addr);
+        const EARLY_CONFIG_V0_4: Item<EarlyConfigV0_4> = Item::new("config");
+
+        // writes state the way v0.3.0 did,
+        // including a withdrawal that has long since been claimed,
+        // or the way v0.4.0 did, with a withdrawal still pending
+        fn legacy_instantiate(
+            deps: DepsMut,
+            env: Env,
+            _info: MessageInfo,
+            msg: LegacyInstantiateMsg,
+        ) -> StdResult<Response> {
+            set_contract_version(
+                deps.storage,
+                "crates.io:cw-unity-prop",
+                &msg.contract_version,
+            )?;
+
+            if msg.contract_version.starts_with("0.3.") {
+                let legacy_config = ConfigV0_3 {
+                    withdraw_address: Addr::unchecked("gordon-gekko-address"),
+                    withdraw_delay_in_days: 28,
+                    native_denom: NATIVE_DENOM.to_string(),
+                };
+                CONFIG_V0_3.save(deps.storage, &legacy_config)?;
+                WITHDRAWAL_READY_V0_4.save(deps.storage, &env.block.time)?;
+            } else {
+                match msg.rewards_destination {
+                    Some(rewards_destination) => {
+                        let legacy_config = ConfigV0_4 {
+                            withdraw_address: Addr::unchecked("gordon-gekko-address"),
+                            withdraw_delay_in_days: 28,
+                            withdraw_claim_window_in_days: Some(7),
+                            native_denom: NATIVE_DENOM.to_string(),
+                            vesting: None,
+                            rewards_destination,
+                        };
+                        CONFIG_V0_4.save(deps.storage, &legacy_config)?;
+                    }
+                    None => {
+                        let legacy_config = EarlyConfigV0_4 {
+                            withdraw_address: Addr::unchecked("gordon-gekko-address"),
+                            withdraw_delay_in_days: 28,
+                            withdraw_claim_window_in_days: Some(7),
+                            native_denom: NATIVE_DENOM.to_string(),
+                            vesting: None,
+                        };
+                        EARLY_CONFIG_V0_4.save(deps.storage, &legacy_config)?;
+                    }
+                }
+                WITHDRAWAL_READY_V0_4
+                    .save(deps.storage, &env.block.time.plus_seconds(28 * 86400))?;
+            }
 
-            // balance now empty
-            assert_eq!(contract_balance, &[]);
+            Ok(Response::new())
+        }
+
+        fn legacy_contract() -> Box<dyn Contract<Empty>> {
+            let contract = ContractWrapper::new(
+                crate::contract::execute,
+                legacy_instantiate,
+                crate::contract::query,
+            );
+            Box::new(contract)
+        }
+
+        fn mock_instantiate_legacy(
+            version: &str,
+            rewards_destination: Option<RewardsDestination>,
+        ) -> (App, Addr, u64) {
+            let mut app = mock_app();
+            let legacy_id = app.store_code(legacy_contract());
+            let new_id = app.store_code(contract_template());
+
+            let msg = LegacyInstantiateMsg {
+                contract_version: version.to_string(),
+                rewards_destination,
+            };
+
+            let contract_addr = app
+                .instantiate_contract(
+                    legacy_id,
+                    Addr::unchecked(USER),
+                    &msg,
+                    &coins(3_000_000, NATIVE_DENOM),
+                    "cw-unity-prop",
+                    Some(USER.to_string()), // migration needs an admin
+                )
+                .unwrap();
+
+            (app, contract_addr, new_id)
         }
 
         #[test]
-        fn sudo_send() {
-            let (mut app, _cw_template_contract, contract_addr) = mock_instantiate(28);
+        fn migrate_from_v0_3_0() {
+            let (mut app, contract_addr, new_id) = mock_instantiate_legacy("0.3.0", None);
 
-            let nominated_address = String::from("carl-fox-address");
-            let validated_addr = Addr::unchecked(&nominated_address);
+            app.update_block(advance_one_day_one_hour);
 
-            // this tests for success
-            exec_sudo_send(
-                &mut app,
+            app.migrate_contract(
+                Addr::unchecked(USER),
                 contract_addr.clone(),
-                validated_addr.to_string(),
-                Uint128::new(2_000_000),
+                &MigrateMsg {},
+                new_id,
             )
             .unwrap();
 
-            let community_nominated_address_balance = get_balance(&mut app, &validated_addr);
-
+            // config picks up the new fields, unset
+            let config: Config = app
+                .wrap()
+                .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetConfig {})
+                .unwrap();
             assert_eq!(
-                community_nominated_address_balance,
-                coins(2_000_000, NATIVE_DENOM),
+                config,
+                Config {
+                    withdraw_address: Addr::unchecked("gordon-gekko-address"),
+                    withdraw_delay: DAY * 28,
+                    withdraw_claim_window: None,
+                    native_denom: NATIVE_DENOM.to_string(),
+                    vesting: None,
+                    rewards_destination: RewardsDestination::Contract,
+                    guardians: None,
+                    emergency_council: None,
+                }
             );
 
-            let contract_balance = get_balance(&mut app, &contract_addr);
+            // the stale withdrawal is gone
+            app.wrap()
+                .query_wasm_smart::<WithdrawalTimestampResponse>(
+                    contract_addr.clone(),
+                    &QueryMsg::GetWithdrawalReadyTime {},
+                )
+                .unwrap_err();
 
-            assert_eq!(contract_balance, coins(1_000_000, NATIVE_DENOM));
+            // and the version is bumped
+            let version: ContractVersion =
+                cw2::query_contract_info(&app.wrap(), &contract_addr).unwrap();
+            assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+
+            // funds are untouched
+            let contract_balance = get_balance(&mut app, &contract_addr);
+            assert_eq!(contract_balance, coins(3_000_000, NATIVE_DENOM));
         }
 
         #[test]
-        fn sudo_send_fails() {
-            let (mut app, _cw_template_contract, contract_addr) = mock_instantiate_no_balance();
-
-            let nominated_address = String::from("carl-fox-address");
-            let validated_addr = Addr::unchecked(&nominated_address);
+        fn migrate_from_v0_4_0() {
+            let (mut app, contract_addr, new_id) =
+                mock_instantiate_legacy("0.4.0", Some(RewardsDestination::WithdrawAddress));
+            let ready_at = app.block_info().time.plus_seconds(28 * 86400);
 
-            // this tests for error
-            let _err = exec_sudo_send(
-                &mut app,
-                contract_addr,
-                validated_addr.to_string(),
-                Uint128::new(2_000_000),
+            app.migrate_contract(
+                Addr::unchecked(USER),
+                contract_addr.clone(),
+                &MigrateMsg {},
+                new_id,
             )
-            .unwrap_err();
-        }
-
-        #[test]
-        fn sudo_send_all() {
-            let (mut app, _cw_template_contract, contract_addr) = mock_instantiate(28);
+            .unwrap();
+
+            // days become seconds
+            let config: Config = app
+                .wrap()
+                .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetConfig {})
+                .unwrap();
+            assert_eq!(
+                config,
+                Config {
+                    withdraw_address: Addr::unchecked("gordon-gekko-address"),
+                    withdraw_delay: DAY * 28,
+                    withdraw_claim_window: Some(DAY * 7),
+                    native_denom: NATIVE_DENOM.to_string(),
+                    vesting: None,
+                    rewards_destination: RewardsDestination::WithdrawAddress,
+                    guardians: None,
+                    emergency_council: None,
+                }
+            );
+
+            // the pending withdrawal keeps its ready time
+            let res: WithdrawalTimestampResponse = app
+                .wrap()
+                .query_wasm_smart(contract_addr, &QueryMsg::GetWithdrawalReadyTime {})
+                .unwrap();
+            assert_eq!(res.withdrawal_ready_at, Expiration::AtTime(ready_at));
+        }
+
+        #[test]
+        fn migrate_from_v0_4_0_before_rewards_destination() {
+            let (mut app, contract_addr, new_id) = mock_instantiate_legacy("0.4.0", None);
+
+            app.migrate_contract(
+                Addr::unchecked(USER),
+                contract_addr.clone(),
+                &MigrateMsg {},
+                new_id,
+            )
+            .unwrap();
+
+            // rewards stay in the contract, as they did then
+            let config: Config = app
+                .wrap()
+                .query_wasm_smart(contract_addr, &QueryMsg::GetConfig {})
+                .unwrap();
+            assert_eq!(config.rewards_destination, RewardsDestination::Contract);
+            assert_eq!(config.withdraw_delay, DAY * 28);
+        }
+
+        #[test]
+        fn migrate_refuses_downgrade() {
+            let (mut app, contract_addr, new_id) = mock_instantiate_legacy("99.0.0", None);
+
+            app.migrate_contract(Addr::unchecked(USER), contract_addr, &MigrateMsg {}, new_id)
+                .unwrap_err();
+        }
+    }
+
+    mod sudo {
+        use super::*;
+
+        use crate::msg::SimulationResponse;
+        use cosmwasm_std::{CosmosMsg, IbcMsg, IbcTimeout};
+
+        #[test]
+        fn frozen_withdrawal_does_not_block_governance() {
+            let (mut app, cw_template_contract, contract_addr) = mock_instantiate(1);
+
+            let validated_addr = Addr::unchecked("gordon-gekko-address");
+
+            let msg = SudoMsg::SetEmergencyCouncil {
+                council: Some(EmergencyCouncilMsg {
+                    address: String::from("lou-mannheim-address"),
+                    freeze_duration: DAY * 7,
+                }),
+            };
+            app.wasm_sudo(contract_addr.clone(), &msg).unwrap();
+
+            let msg = ExecuteMsg::StartWithdraw { amount: None };
+            let cosmos_msg = cw_template_contract.call(msg).unwrap();
+            app.execute(validated_addr.clone(), cosmos_msg).unwrap();
+
+            let msg = ExecuteMsg::Freeze {};
+            let cosmos_msg = cw_template_contract.call(msg).unwrap();
+            app.execute(Addr::unchecked("lou-mannheim-address"), cosmos_msg)
+                .unwrap();
+
+            app.update_block(advance_one_day_one_hour);
+
+            // the withdraw_address is held up
+            let claim_msg = ExecuteMsg::ExecuteWithdraw {};
+            let claim_msg_res = cw_template_contract.call(claim_msg).unwrap();
+            app.execute(validated_addr.clone(), claim_msg_res)
+                .unwrap_err();
+
+            // while governance moves the funds somewhere safe
+            exec_sudo_send_all(
+                &mut app,
+                contract_addr.clone(),
+                String::from("carl-fox-address"),
+            )
+            .unwrap();
+
+            assert_eq!(get_balance(&mut app, &contract_addr), &[]);
+            assert_eq!(get_balance(&mut app, &validated_addr), &[]);
+        }
+
+        #[test]
+        fn sudo_set_guardians_then_approve_and_claim() {
+            let (mut app, cw_template_contract, contract_addr) = mock_instantiate(1);
+
+            let validated_addr = Addr::unchecked("gordon-gekko-address");
+
+            let msg = SudoMsg::SetGuardians {
+                guardians: vec![
+                    String::from("carl-fox-address"),
+                    String::from("lou-mannheim-address"),
+                ],
+                threshold: 1,
+            };
+            app.wasm_sudo(contract_addr.clone(), &msg).unwrap();
+
+            let msg = ExecuteMsg::StartWithdraw { amount: None };
+            let cosmos_msg = cw_template_contract.call(msg).unwrap();
+            app.execute(validated_addr.clone(), cosmos_msg).unwrap();
+
+            app.update_block(advance_one_day_one_hour);
+
+            // the delay has passed, but no guardian has approved
+            let claim_msg = ExecuteMsg::ExecuteWithdraw {};
+            let claim_msg_res = cw_template_contract.call(claim_msg).unwrap();
+            app.execute(validated_addr.clone(), claim_msg_res.clone())
+                .unwrap_err();
+
+            let msg = ExecuteMsg::ApproveWithdrawal {};
+            let cosmos_msg = cw_template_contract.call(msg).unwrap();
+            app.execute(Addr::unchecked("lou-mannheim-address"), cosmos_msg)
+                .unwrap();
+
+            app.execute(validated_addr.clone(), claim_msg_res).unwrap();
+
+            assert_eq!(
+                get_balance(&mut app, &validated_addr),
+                coins(3_000_000, NATIVE_DENOM)
+            );
+        }
+
+        #[test]
+        fn sudo_burn() {
+            let (mut app, _cw_template_contract, contract_addr) = mock_instantiate(28);
+
+            // this tests for success
+            exec_sudo_burn(&mut app, contract_addr.clone(), None).unwrap();
+
+            let contract_balance = get_balance(&mut app, &contract_addr);
+
+            // balance now empty
+            assert_eq!(contract_balance, &[]);
+        }
+
+        #[test]
+        fn sudo_burn_partial() {
+            let (mut app, _cw_template_contract, contract_addr) = mock_instantiate(28);
+
+            // this tests for success
+            exec_sudo_burn(
+                &mut app,
+                contract_addr.clone(),
+                Some(coins(1_000_000, NATIVE_DENOM)),
+            )
+            .unwrap();
+
+            let contract_balance = get_balance(&mut app, &contract_addr);
+
+            // only what was asked for has gone
+            assert_eq!(contract_balance, coins(2_000_000, NATIVE_DENOM));
+
+            // this tests for error
+            let _err = exec_sudo_burn(
+                &mut app,
+                contract_addr,
+                Some(coins(3_000_000, NATIVE_DENOM)),
+            )
+            .unwrap_err();
+        }
+
+        #[test]
+        fn sudo_send() {
+            let (mut app, _cw_template_contract, contract_addr) = mock_instantiate(28);
+
+            let nominated_address = String::from("carl-fox-address");
+            let validated_addr = Addr::unchecked(&nominated_address);
+
+            // this tests for success
+            exec_sudo_send(
+                &mut app,
+                contract_addr.clone(),
+                validated_addr.to_string(),
+                Uint128::new(2_000_000),
+            )
+            .unwrap();
+
+            let community_nominated_address_balance = get_balance(&mut app, &validated_addr);
+
+            assert_eq!(
+                community_nominated_address_balance,
+                coins(2_000_000, NATIVE_DENOM),
+            );
+
+            let contract_balance = get_balance(&mut app, &contract_addr);
+
+            assert_eq!(contract_balance, coins(1_000_000, NATIVE_DENOM));
+        }
+
+        #[test]
+        fn sudo_send_fails() {
+            let (mut app, _cw_template_contract, contract_addr) = mock_instantiate_no_balance();
+
+            let nominated_address = String::from("carl-fox-address");
+            let validated_addr = Addr::unchecked(&nominated_address);
+
+            // this tests for error
+            let _err = exec_sudo_send(
+                &mut app,
+                contract_addr,
+                validated_addr.to_string(),
+                Uint128::new(2_000_000),
+            )
+            .unwrap_err();
+        }
+
+        #[test]
+        fn sudo_send_all() {
+            let (mut app, _cw_template_contract, contract_addr) = mock_instantiate(28);
 
             let nominated_address = String::from("carl-fox-address");
             let validated_addr = Addr::unchecked(&nominated_address);
@@ -457,6 +1201,240 @@ mod tests {
                 .unwrap_err();
         }
 
+        #[test]
+        fn sudo_split_send() {
+            let (mut app, _cw_template_contract, contract_addr) = mock_instantiate(28);
+
+            let carl_fox = Addr::unchecked("carl-fox-address");
+            let bluestar = Addr::unchecked("bluestar-airlines");
+
+            // this tests for success
+            let msg = SudoMsg::ExecuteSplitSend {
+                recipients: vec![
+                    (carl_fox.to_string(), Uint128::new(2_000_000)),
+                    (bluestar.to_string(), Uint128::new(1_000_000)),
+                ],
+            };
+            app.wasm_sudo(contract_addr.clone(), &msg).unwrap();
+
+            assert_eq!(
+                get_balance(&mut app, &carl_fox),
+                coins(2_000_000, NATIVE_DENOM)
+            );
+            assert_eq!(
+                get_balance(&mut app, &bluestar),
+                coins(1_000_000, NATIVE_DENOM)
+            );
+
+            let contract_balance = get_balance(&mut app, &contract_addr);
+
+            // contract balance now empty
+            assert_eq!(contract_balance, &[]);
+        }
+
+        #[test]
+        fn sudo_split_send_fails_atomically() {
+            let (mut app, _cw_template_contract, contract_addr) = mock_instantiate(28);
+
+            let carl_fox = Addr::unchecked("carl-fox-address");
+
+            // this tests for error
+            let msg = SudoMsg::ExecuteSplitSend {
+                recipients: vec![
+                    (carl_fox.to_string(), Uint128::new(1_000_000)),
+                    (String::from("BLUESTAR-AIRLINES"), Uint128::new(1_000_000)),
+                ],
+            };
+            let _err = app.wasm_sudo(contract_addr.clone(), &msg).unwrap_err();
+
+            // nobody was paid
+            assert_eq!(get_balance(&mut app, &carl_fox), &[]);
+            assert_eq!(
+                get_balance(&mut app, &contract_addr),
+                coins(3_000_000, NATIVE_DENOM)
+            );
+        }
+
+        #[test]
+        fn sudo_ibc_transfer() {
+            let (mut app, _cw_template_contract, contract_addr) = mock_instantiate(28);
+
+            // this tests for success
+            let msg = SudoMsg::IbcTransfer {
+                channel_id: String::from("channel-0"),
+                to_address: String::from("cosmos1carlfox"),
+                amount: coin(2_000_000, NATIVE_DENOM),
+                timeout_seconds: 600,
+            };
+
+            // the ibc module here accepts anything,
+            // so check what is actually sent by simulating it first
+            let simulation: SimulationResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    contract_addr.clone(),
+                    &QueryMsg::SimulateSudo { msg: msg.clone() },
+                )
+                .unwrap();
+            let timeout = app.block_info().time.plus_seconds(600);
+            assert_eq!(
+                simulation.messages,
+                vec![CosmosMsg::Ibc(IbcMsg::Transfer {
+                    channel_id: String::from("channel-0"),
+                    to_address: String::from("cosmos1carlfox"),
+                    amount: coin(2_000_000, NATIVE_DENOM),
+                    timeout: IbcTimeout::with_timestamp(timeout),
+                })]
+            );
+
+            let res = app.wasm_sudo(contract_addr.clone(), &msg).unwrap();
+
+            let wasm = res.events.iter().find(|ev| ev.ty == "wasm").unwrap();
+            assert!(wasm
+                .attributes
+                .iter()
+                .any(|attr| attr.key == "action" && attr.value == "ibc_transfer"));
+
+            // this tests for error
+            let msg = SudoMsg::IbcTransfer {
+                channel_id: String::from("channel-0"),
+                to_address: String::from("cosmos1carlfox"),
+                amount: coin(4_000_000, NATIVE_DENOM),
+                timeout_seconds: 600,
+            };
+            let _err = app.wasm_sudo(contract_addr, &msg).unwrap_err();
+        }
+
+        #[test]
+        fn sudo_fund_community_pool() {
+            let (mut app, _cw_template_contract, contract_addr) = mock_instantiate(28);
+
+            // this tests for success
+            exec_sudo_fund_community_pool(&mut app, contract_addr.clone(), None).unwrap();
+
+            let community_pool_balance = get_balance(&mut app, &Addr::unchecked(COMMUNITY_POOL));
+
+            assert_eq!(community_pool_balance, coins(3_000_000, NATIVE_DENOM));
+
+            let contract_balance = get_balance(&mut app, &contract_addr);
+
+            // contract balance now empty
+            assert_eq!(contract_balance, &[]);
+        }
+
+        #[test]
+        fn sudo_fund_community_pool_partial() {
+            let (mut app, _cw_template_contract, contract_addr) = mock_instantiate(28);
+
+            // this tests for success
+            exec_sudo_fund_community_pool(
+                &mut app,
+                contract_addr.clone(),
+                Some(coins(1_000_000, NATIVE_DENOM)),
+            )
+            .unwrap();
+
+            let community_pool_balance = get_balance(&mut app, &Addr::unchecked(COMMUNITY_POOL));
+
+            assert_eq!(community_pool_balance, coins(1_000_000, NATIVE_DENOM));
+
+            let contract_balance = get_balance(&mut app, &contract_addr);
+
+            assert_eq!(contract_balance, coins(2_000_000, NATIVE_DENOM));
+        }
+
+        #[test]
+        fn sudo_fund_community_pool_fails() {
+            let (mut app, _cw_template_contract, contract_addr) = mock_instantiate_no_balance();
+
+            // this tests for error
+            let _err = exec_sudo_fund_community_pool(&mut app, contract_addr, None).unwrap_err();
+        }
+
+        #[test]
+        fn sudo_actions_then_withdraw_add_to_totals() {
+            let (mut app, cw_template_contract, contract_addr) = mock_instantiate(1);
+
+            let validated_addr = Addr::unchecked("gordon-gekko-address");
+
+            exec_sudo_burn(
+                &mut app,
+                contract_addr.clone(),
+                Some(coins(500_000, NATIVE_DENOM)),
+            )
+            .unwrap();
+            exec_sudo_send(
+                &mut app,
+                contract_addr.clone(),
+                String::from("carl-fox-address"),
+                Uint128::new(500_000),
+            )
+            .unwrap();
+            exec_sudo_fund_community_pool(
+                &mut app,
+                contract_addr.clone(),
+                Some(coins(500_000, NATIVE_DENOM)),
+            )
+            .unwrap();
+
+            // the withdraw address takes the rest
+            let msg = ExecuteMsg::StartWithdraw { amount: None };
+            let cosmos_msg = cw_template_contract.call(msg).unwrap();
+            app.execute(validated_addr.clone(), cosmos_msg).unwrap();
+
+            app.update_block(advance_one_day_one_hour);
+
+            let claim_msg = ExecuteMsg::ExecuteWithdraw {};
+            let claim_msg_res = cw_template_contract.call(claim_msg).unwrap();
+            app.execute(validated_addr, claim_msg_res).unwrap();
+
+            let totals: Totals = app
+                .wrap()
+                .query_wasm_smart(contract_addr, &QueryMsg::GetTotals {})
+                .unwrap();
+
+            assert_eq!(
+                totals,
+                Totals {
+                    withdrawn: coins(1_500_000, NATIVE_DENOM),
+                    burned: coins(500_000, NATIVE_DENOM),
+                    sent: coins(500_000, NATIVE_DENOM),
+                    sent_to_community_pool: coins(500_000, NATIVE_DENOM),
+                }
+            );
+        }
+
+        #[test]
+        fn sudo_update_config_then_withdraw() {
+            let (mut app, cw_template_contract, contract_addr) = mock_instantiate(28);
+
+            let nominated_address = String::from("carl-fox-address");
+            let validated_addr = Addr::unchecked(&nominated_address);
+
+            // community rotates the beneficiary and shortens the delay
+            let msg = SudoMsg::UpdateConfig {
+                withdraw_address: Some(nominated_address),
+                withdraw_delay: Some(DAY * 1),
+                native_denom: None,
+                rewards_destination: None,
+            };
+            app.wasm_sudo(contract_addr.clone(), &msg).unwrap();
+
+            let msg = ExecuteMsg::StartWithdraw { amount: None };
+            let cosmos_msg = cw_template_contract.call(msg).unwrap();
+            app.execute(validated_addr.clone(), cosmos_msg).unwrap();
+
+            // move time forward
+            app.update_block(advance_one_day_one_hour);
+
+            let claim_msg = ExecuteMsg::ExecuteWithdraw {};
+            let claim_msg_res = cw_template_contract.call(claim_msg).unwrap();
+            app.execute(validated_addr.clone(), claim_msg_res).unwrap();
+
+            let nominated_address_balance = get_balance(&mut app, &validated_addr);
+            assert_eq!(nominated_address_balance, coins(3_000_000, NATIVE_DENOM));
+        }
+
         #[test]
         fn sudo_send_fails_balance_too_small() {
             let (mut app, _cw_template_contract, contract_addr) = mock_instantiate(28);
@@ -474,4 +1452,475 @@ mod tests {
             .unwrap_err();
         }
     }
+
+    mod cw20_tokens {
+        use super::*;
+
+        use crate::msg::{Cw20BalancesResponse, SimulationResponse};
+        use cosmwasm_std::Binary;
+        use cw20::{Cw20Coin, Cw20CoinVerified, Cw20Contract, Cw20ExecuteMsg};
+
+        fn cw20_template() -> Box<dyn Contract<Empty>> {
+            let contract = ContractWrapper::new(
+                cw20_base::contract::execute,
+                cw20_base::contract::instantiate,
+                cw20_base::contract::query,
+            );
+            Box::new(contract)
+        }
+
+        // sets up the contract as usual,
+        // then has bud fox send it some cw20 tokens
+        fn mock_instantiate_with_untracked_cw20() -> (App, CwTemplateContract, Addr, Addr) {
+            let (mut app, cw_template_contract, contract_addr) = mock_instantiate(1);
+            let cw20_id = app.store_code(cw20_template());
+
+            let msg = cw20_base::msg::InstantiateMsg {
+                name: String::from("Bluestar Airlines"),
+                symbol: String::from("BSTAR"),
+                decimals: 6,
+                initial_balances: vec![Cw20Coin {
+                    address: String::from("bud-fox-address"),
+                    amount: Uint128::new(1_000_000),
+                }],
+                mint: None,
+                marketing: None,
+            };
+            let token_addr = app
+                .instantiate_contract(cw20_id, Addr::unchecked(USER), &msg, &[], "bstar", None)
+                .unwrap();
+
+            let msg = Cw20ExecuteMsg::Send {
+                contract: contract_addr.to_string(),
+                amount: Uint128::new(1_000_000),
+                msg: Binary::default(),
+            };
+            app.execute_contract(
+                Addr::unchecked("bud-fox-address"),
+                token_addr.clone(),
+                &msg,
+                &[],
+            )
+            .unwrap();
+
+            (app, cw_template_contract, contract_addr, token_addr)
+        }
+
+        // as above, with governance tracking the token
+        fn mock_instantiate_with_cw20() -> (App, CwTemplateContract, Addr, Addr) {
+            let (mut app, cw_template_contract, contract_addr, token_addr) =
+                mock_instantiate_with_untracked_cw20();
+
+            let msg = SudoMsg::TrackCw20 {
+                token: token_addr.to_string(),
+            };
+            app.wasm_sudo(contract_addr.clone(), &msg).unwrap();
+
+            (app, cw_template_contract, contract_addr, token_addr)
+        }
+
+        fn get_cw20_balance(app: &mut App, token_addr: &Addr, address: &Addr) -> Uint128 {
+            Cw20Contract(token_addr.clone())
+                .balance(&app.wrap(), address)
+                .unwrap()
+        }
+
+        #[test]
+        fn receive_cw20_is_tracked_by_governance() {
+            let (mut app, _cw_template_contract, contract_addr, token_addr) =
+                mock_instantiate_with_untracked_cw20();
+
+            // receiving only records the deposit
+            let msg = QueryMsg::GetCw20Balances {};
+            let res: Cw20BalancesResponse = app
+                .wrap()
+                .query_wasm_smart(contract_addr.clone(), &msg)
+                .unwrap();
+            assert_eq!(res.balances, vec![]);
+
+            let msg = SudoMsg::TrackCw20 {
+                token: token_addr.to_string(),
+            };
+            app.wasm_sudo(contract_addr.clone(), &msg).unwrap();
+
+            let msg = QueryMsg::GetCw20Balances {};
+            let res: Cw20BalancesResponse =
+                app.wrap().query_wasm_smart(contract_addr, &msg).unwrap();
+
+            assert_eq!(
+                res.balances,
+                vec![Cw20CoinVerified {
+                    address: token_addr,
+                    amount: Uint128::new(1_000_000),
+                }]
+            );
+        }
+
+        #[test]
+        fn start_withdraw_then_claim_includes_cw20() {
+            let (mut app, cw_template_contract, contract_addr, token_addr) =
+                mock_instantiate_with_cw20();
+
+            let validated_addr = Addr::unchecked("gordon-gekko-address");
+
+            let msg = ExecuteMsg::StartWithdraw { amount: None };
+            let cosmos_msg = cw_template_contract.call(msg).unwrap();
+            app.execute(validated_addr.clone(), cosmos_msg).unwrap();
+
+            // move time forward
+            app.update_block(advance_one_day_one_hour);
+
+            let claim_msg = ExecuteMsg::ExecuteWithdraw {};
+            let claim_msg_res = cw_template_contract.call(claim_msg).unwrap();
+            app.execute(validated_addr.clone(), claim_msg_res).unwrap();
+
+            // native and cw20 funds have both moved
+            assert_eq!(
+                get_balance(&mut app, &validated_addr),
+                coins(3_000_000, NATIVE_DENOM)
+            );
+            assert_eq!(
+                get_cw20_balance(&mut app, &token_addr, &validated_addr),
+                Uint128::new(1_000_000)
+            );
+            assert_eq!(
+                get_cw20_balance(&mut app, &token_addr, &contract_addr),
+                Uint128::zero()
+            );
+        }
+
+        #[test]
+        fn sudo_send_all_includes_cw20() {
+            let (mut app, _cw_template_contract, contract_addr, token_addr) =
+                mock_instantiate_with_cw20();
+
+            let validated_addr = Addr::unchecked("carl-fox-address");
+
+            exec_sudo_send_all(&mut app, contract_addr.clone(), validated_addr.to_string())
+                .unwrap();
+
+            assert_eq!(
+                get_cw20_balance(&mut app, &token_addr, &validated_addr),
+                Uint128::new(1_000_000)
+            );
+            assert_eq!(get_balance(&mut app, &contract_addr), &[]);
+        }
+
+        #[test]
+        fn simulate_sudo_send_all_matches_execution() {
+            let (mut app, _cw_template_contract, contract_addr, token_addr) =
+                mock_instantiate_with_cw20();
+
+            let validated_addr = Addr::unchecked("carl-fox-address");
+
+            let msg = QueryMsg::SimulateSudo {
+                msg: SudoMsg::ExecuteSendAll {
+                    recipient: validated_addr.to_string(),
+                },
+            };
+            let res: SimulationResponse = app
+                .wrap()
+                .query_wasm_smart(contract_addr.clone(), &msg)
+                .unwrap();
+            assert_eq!(res.error, None);
+            assert_eq!(res.messages.len(), 2);
+
+            // simulating moves nothing
+            assert_eq!(
+                get_cw20_balance(&mut app, &token_addr, &contract_addr),
+                Uint128::new(1_000_000)
+            );
+
+            // executing the simulated messages gives the same result as the sudo message
+            for msg in res.messages {
+                app.execute(contract_addr.clone(), msg).unwrap();
+            }
+            assert_eq!(
+                get_balance(&mut app, &validated_addr),
+                coins(3_000_000, NATIVE_DENOM)
+            );
+            assert_eq!(
+                get_cw20_balance(&mut app, &token_addr, &validated_addr),
+                Uint128::new(1_000_000)
+            );
+        }
+
+        #[test]
+        fn sudo_send_cw20() {
+            let (mut app, _cw_template_contract, contract_addr, token_addr) =
+                mock_instantiate_with_cw20();
+
+            let validated_addr = Addr::unchecked("carl-fox-address");
+
+            // this tests for success
+            let msg = SudoMsg::ExecuteSendCw20 {
+                token: token_addr.to_string(),
+                recipient: validated_addr.to_string(),
+                amount: Uint128::new(400_000),
+            };
+            app.wasm_sudo(contract_addr.clone(), &msg).unwrap();
+
+            assert_eq!(
+                get_cw20_balance(&mut app, &token_addr, &validated_addr),
+                Uint128::new(400_000)
+            );
+            assert_eq!(
+                get_cw20_balance(&mut app, &token_addr, &contract_addr),
+                Uint128::new(600_000)
+            );
+
+            // this tests for error
+            let msg = SudoMsg::ExecuteSendCw20 {
+                token: token_addr.to_string(),
+                recipient: validated_addr.to_string(),
+                amount: Uint128::new(700_000),
+            };
+            let _err = app.wasm_sudo(contract_addr, &msg).unwrap_err();
+        }
+
+        #[test]
+        fn sudo_burn_cw20() {
+            let (mut app, _cw_template_contract, contract_addr, token_addr) =
+                mock_instantiate_with_cw20();
+
+            // burn some
+            let msg = SudoMsg::BurnCw20 {
+                token: token_addr.to_string(),
+                amount: Some(Uint128::new(250_000)),
+            };
+            app.wasm_sudo(contract_addr.clone(), &msg).unwrap();
+
+            assert_eq!(
+                get_cw20_balance(&mut app, &token_addr, &contract_addr),
+                Uint128::new(750_000)
+            );
+
+            // then the rest
+            let msg = SudoMsg::BurnCw20 {
+                token: token_addr.to_string(),
+                amount: None,
+            };
+            app.wasm_sudo(contract_addr.clone(), &msg).unwrap();
+
+            assert_eq!(
+                get_cw20_balance(&mut app, &token_addr, &contract_addr),
+                Uint128::zero()
+            );
+
+            // nothing left to burn
+            let _err = app.wasm_sudo(contract_addr, &msg).unwrap_err();
+        }
+    }
+
+    mod beneficiaries {
+        use super::*;
+
+        use crate::msg::{BeneficiariesResponse, BeneficiaryShare};
+        use cw_multi_test::{BankSudo, SudoMsg as MultiTestSudoMsg};
+
+        // bud fox holds one share, carl fox two
+        fn mock_instantiate_beneficiaries() -> (App, CwTemplateContract, Addr) {
+            let mut app = mock_app();
+            let cw_template_id = app.store_code(contract_template());
+
+            let msg = InstantiateMsg {
+                withdraw_address: String::from("gordon-gekko-address"),
+                withdraw_delay: DAY,
+                withdraw_claim_window: None,
+                native_denom: NATIVE_DENOM.to_string(),
+                vesting: None,
+                rewards_destination: None,
+                emergency_council: None,
+                beneficiaries: Some(vec![
+                    BeneficiaryShare {
+                        address: String::from("bud-fox-address"),
+                        shares: 1,
+                    },
+                    BeneficiaryShare {
+                        address: String::from("carl-fox-address"),
+                        shares: 2,
+                    },
+                ]),
+            };
+
+            let cw_template_contract_addr = app
+                .instantiate_contract(
+                    cw_template_id,
+                    Addr::unchecked(USER),
+                    &msg,
+                    &coins(3_000_000, NATIVE_DENOM),
+                    "cw-unity-prop",
+                    None,
+                )
+                .unwrap();
+
+            let cw_template_contract = CwTemplateContract(cw_template_contract_addr.clone());
+
+            (app, cw_template_contract, cw_template_contract_addr)
+        }
+
+        fn start_and_claim(
+            app: &mut App,
+            cw_template_contract: &CwTemplateContract,
+            beneficiary: &Addr,
+        ) {
+            let msg = ExecuteMsg::StartWithdraw { amount: None };
+            let cosmos_msg = cw_template_contract.call(msg).unwrap();
+            app.execute(beneficiary.clone(), cosmos_msg).unwrap();
+
+            app.update_block(advance_one_day_one_hour);
+
+            let claim_msg = ExecuteMsg::ExecuteWithdraw {};
+            let claim_msg_res = cw_template_contract.call(claim_msg).unwrap();
+            app.execute(beneficiary.clone(), claim_msg_res).unwrap();
+        }
+
+        #[test]
+        fn beneficiaries_claim_their_share() {
+            let (mut app, cw_template_contract, contract_addr) = mock_instantiate_beneficiaries();
+
+            let bud_fox = Addr::unchecked("bud-fox-address");
+            let carl_fox = Addr::unchecked("carl-fox-address");
+
+            // the withdraw_address is not a beneficiary
+            let msg = ExecuteMsg::StartWithdraw { amount: None };
+            let cosmos_msg = cw_template_contract.call(msg).unwrap();
+            app.execute(Addr::unchecked("gordon-gekko-address"), cosmos_msg)
+                .unwrap_err();
+
+            // a third of 3_000_000
+            start_and_claim(&mut app, &cw_template_contract, &bud_fox);
+            assert_eq!(
+                get_balance(&mut app, &bud_fox),
+                coins(1_000_000, NATIVE_DENOM)
+            );
+
+            // more funds arrive
+            app.sudo(MultiTestSudoMsg::Bank(BankSudo::Mint {
+                to_address: contract_addr.to_string(),
+                amount: coins(3_000_000, NATIVE_DENOM),
+            }))
+            .unwrap();
+
+            // two thirds of everything, as claiming second costs nothing
+            start_and_claim(&mut app, &cw_template_contract, &carl_fox);
+            assert_eq!(
+                get_balance(&mut app, &carl_fox),
+                coins(4_000_000, NATIVE_DENOM)
+            );
+
+            // and bud fox's share of the new funds is still there
+            let res: BeneficiariesResponse = app
+                .wrap()
+                .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetBeneficiaries {})
+                .unwrap();
+            assert_eq!(res.beneficiaries[0].address, bud_fox);
+            assert_eq!(res.beneficiaries[0].claimable, Uint128::new(1_000_000));
+            assert_eq!(res.beneficiaries[1].claimable, Uint128::zero());
+
+            start_and_claim(&mut app, &cw_template_contract, &bud_fox);
+            assert_eq!(
+                get_balance(&mut app, &bud_fox),
+                coins(2_000_000, NATIVE_DENOM)
+            );
+            assert_eq!(get_balance(&mut app, &contract_addr), &[]);
+        }
+
+        #[test]
+        fn beneficiary_claim_after_governance_send() {
+            let (mut app, cw_template_contract, contract_addr) = mock_instantiate_beneficiaries();
+
+            let bud_fox = Addr::unchecked("bud-fox-address");
+            let carl_fox = Addr::unchecked("carl-fox-address");
+
+            // two thirds of 3_000_000
+            start_and_claim(&mut app, &cw_template_contract, &carl_fox);
+            assert_eq!(
+                get_balance(&mut app, &carl_fox),
+                coins(2_000_000, NATIVE_DENOM)
+            );
+
+            // governance moves most of the rest out
+            let msg = SudoMsg::ExecuteSend {
+                recipient: String::from("lou-mannheim-address"),
+                amount: Uint128::new(900_000),
+                denom: None,
+            };
+            app.wasm_sudo(contract_addr.clone(), &msg).unwrap();
+
+            // bud fox can only claim what is left
+            let res: BeneficiariesResponse = app
+                .wrap()
+                .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetBeneficiaries {})
+                .unwrap();
+            assert_eq!(res.beneficiaries[0].claimable, Uint128::new(100_000));
+
+            start_and_claim(&mut app, &cw_template_contract, &bud_fox);
+            assert_eq!(
+                get_balance(&mut app, &bud_fox),
+                coins(100_000, NATIVE_DENOM)
+            );
+            assert_eq!(get_balance(&mut app, &contract_addr), &[]);
+
+            // the rest of bud fox's share stays owed, and is paid once refilled
+            app.sudo(MultiTestSudoMsg::Bank(BankSudo::Mint {
+                to_address: contract_addr.to_string(),
+                amount: coins(2_700_000, NATIVE_DENOM),
+            }))
+            .unwrap();
+
+            let res: BeneficiariesResponse = app
+                .wrap()
+                .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetBeneficiaries {})
+                .unwrap();
+            assert_eq!(res.beneficiaries[0].claimable, Uint128::new(1_500_000));
+            assert_eq!(res.beneficiaries[1].claimable, Uint128::new(1_200_000));
+
+            start_and_claim(&mut app, &cw_template_contract, &bud_fox);
+            assert_eq!(
+                get_balance(&mut app, &bud_fox),
+                coins(1_600_000, NATIVE_DENOM)
+            );
+        }
+
+        #[test]
+        fn sudo_veto_blocks_beneficiary_claim() {
+            let (mut app, cw_template_contract, contract_addr) = mock_instantiate_beneficiaries();
+
+            let bud_fox = Addr::unchecked("bud-fox-address");
+
+            let msg = ExecuteMsg::StartWithdraw { amount: None };
+            let cosmos_msg = cw_template_contract.call(msg).unwrap();
+            app.execute(bud_fox.clone(), cosmos_msg).unwrap();
+
+            app.update_block(advance_one_day_one_hour);
+
+            let veto_msg = SudoMsg::VetoWithdrawal {
+                reason: String::from("the SEC is asking questions"),
+                beneficiary: Some(bud_fox.to_string()),
+            };
+            app.wasm_sudo(contract_addr.clone(), &veto_msg).unwrap();
+
+            let claim_msg = ExecuteMsg::ExecuteWithdraw {};
+            let claim_msg_res = cw_template_contract.call(claim_msg).unwrap();
+            app.execute(bud_fox.clone(), claim_msg_res).unwrap_err();
+
+            let res: WithdrawalReadyResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    contract_addr.clone(),
+                    &QueryMsg::IsWithdrawalReady {
+                        beneficiary: Some(bud_fox.to_string()),
+                    },
+                )
+                .unwrap();
+            assert!(!res.is_withdrawal_ready);
+
+            assert_eq!(get_balance(&mut app, &bud_fox), &[]);
+            assert_eq!(
+                get_balance(&mut app, &contract_addr),
+                coins(3_000_000, NATIVE_DENOM)
+            );
+        }
+    }
 }
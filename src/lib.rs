@@ -3,6 +3,7 @@ mod error;
 pub mod helpers;
 pub mod integration_tests;
 pub mod msg;
+mod simulation;
 pub mod state;
 pub mod unit_tests;
 
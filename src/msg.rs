@@ -1,26 +1,87 @@
-use cosmwasm_std::{Timestamp, Uint128};
+use cosmwasm_std::{Addr, Coin, CosmosMsg, Uint128};
+use cw20::{Cw20CoinVerified, Cw20ReceiveMsg};
+use cw_utils::{Duration, Expiration};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::state::{ActionRecord, RewardsDestination, VestingSchedule};
+
 /// Basic configuration for the contract
 /// The contract will have no admin so this will need to be set correctly
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub withdraw_address: String, // the address whose funds are locked in this contract
-    pub withdraw_delay_in_days: u64, // withdraw delay in days
-    pub native_denom: String,     // native chain denom - presumably ujuno
+    pub withdraw_delay: Duration, // withdraw delay, in blocks or seconds
+    pub withdraw_claim_window: Option<Duration>, // how long a ready withdrawal stays claimable, in the same unit as the delay - forever if unset
+    pub native_denom: String,                    // native chain denom - presumably ujuno
+    pub vesting: Option<VestingSchedule>, // optional linear release instead of the withdraw delay
+    pub rewards_destination: Option<RewardsDestination>, // where claimed staking rewards go - the contract if unset
+    pub emergency_council: Option<EmergencyCouncilMsg>, // who can freeze withdrawals, and for how long - nobody if unset
+    pub beneficiaries: Option<Vec<BeneficiaryShare>>, // splits the native balance between several addresses - the withdraw_address gets everything if unset
+}
+
+/// An address and its weight when splitting the native balance
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BeneficiaryShare {
+    pub address: String,
+    pub shares: u64,
+}
+
+/// An address that can freeze withdrawals,
+/// and how long each freeze lasts
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EmergencyCouncilMsg {
+    pub address: String,
+    pub freeze_duration: Duration,
 }
 
+/// Migrations carry no parameters,
+/// any state changes are driven by the stored contract version
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     /// Can be run by the admin_address
     /// Starts the withdraw process and creates a timestamp
     /// of when the funds will be ready for claim
-    StartWithdraw {},
+    /// If an amount is given, only that amount will be claimable,
+    /// otherwise the claim will be for everything held by the contract
+    StartWithdraw { amount: Option<Vec<Coin>> },
     /// When the funds are ready to be claimed,
     /// this allows them to actually be claimed
+    /// If a vesting schedule is configured, this claims
+    /// whatever has vested so far, with no need to start a withdrawal
     ExecuteWithdraw {},
+    /// Can be run by the withdraw_address
+    /// Retracts a pending withdrawal
+    CancelWithdraw {},
+    /// Can be run by a guardian, if governance has set any
+    /// Approves the pending withdrawal, which cannot be claimed
+    /// until enough guardians have done so
+    ApproveWithdrawal {},
+    /// Can be run by the emergency council, if there is one
+    /// Blocks ExecuteWithdraw until governance unfreezes,
+    /// or the freeze duration passes
+    /// A freeze cannot be extended, and the council cannot freeze again
+    /// until the freeze duration has passed once more after it ends
+    Freeze {},
+    /// Can be run by the withdraw_address
+    /// Nominates a new withdraw_address, which must then accept
+    ProposeNewWithdrawAddress { address: String },
+    /// Can be run by the nominated address
+    /// Completes the handover and clears any pending withdrawal
+    AcceptWithdrawAddress {},
+    /// Can be run by anyone
+    /// Withdraws staking rewards from every validator the contract
+    /// delegates to, and routes them to the configured destination
+    ClaimRewards {},
+    /// Called by a cw20 contract when tokens are sent here
+    /// Records the deposit, if the sender answers a cw20 TokenInfo query
+    /// Governance has to track the token with TrackCw20
+    /// for a full withdrawal to include it
+    Receive(Cw20ReceiveMsg),
 }
 
 /// This should only be sudo-callable by the governance
@@ -28,14 +89,104 @@ pub enum ExecuteMsg {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum SudoMsg {
-    /// Executes an immediate burn of any funds held by the contract
-    ExecuteBurn {},
+    /// Executes an immediate burn of funds held by the contract
+    /// either the specified amount, or if unset all funds
+    ExecuteBurn { amount: Option<Vec<Coin>> },
     /// Sends the specified amount from the contract balance
-    /// to a nominated address
-    ExecuteSend { recipient: String, amount: Uint128 },
+    /// to a nominated address. Sends the native denom
+    /// unless another denom is given
+    ExecuteSend {
+        recipient: String,
+        amount: Uint128,
+        denom: Option<String>,
+    },
     /// Sends all funds held by the contract
     /// to a nominated address
     ExecuteSendAll { recipient: String },
+    /// Sends native funds to several recipients at once,
+    /// as (address, amount) pairs. Fails as a whole
+    /// if any address is invalid or the balance is too small
+    ExecuteSplitSend { recipients: Vec<(String, Uint128)> },
+    /// Retracts any pending withdrawal
+    /// without touching the funds held by the contract
+    /// With beneficiaries, retracts the named beneficiary's withdrawal,
+    /// or if unset every pending beneficiary withdrawal
+    CancelWithdraw { beneficiary: Option<String> },
+    /// Blocks the pending withdrawal from being claimed
+    /// The withdraw_address must start a fresh withdrawal to try again
+    /// With beneficiaries, blocks the named beneficiary's withdrawal,
+    /// or if unset every pending beneficiary withdrawal
+    VetoWithdrawal {
+        reason: String,
+        beneficiary: Option<String>,
+    },
+    /// Updates the contract config
+    /// Any field left unset keeps its current value
+    /// Changing the withdraw_address clears any pending withdrawal
+    UpdateConfig {
+        withdraw_address: Option<String>,
+        withdraw_delay: Option<Duration>,
+        native_denom: Option<String>,
+        rewards_destination: Option<RewardsDestination>,
+    },
+    /// Sends the specified amount, or if unset all funds,
+    /// held by the contract to the chain's community pool
+    FundCommunityPool { amount: Option<Vec<Coin>> },
+    /// Sends the specified amount of a cw20 token
+    /// held by the contract to a nominated address
+    ExecuteSendCw20 {
+        token: String,
+        recipient: String,
+        amount: Uint128,
+    },
+    /// Burns the specified amount, or if unset all,
+    /// of a cw20 token held by the contract
+    BurnCw20 {
+        token: String,
+        amount: Option<Uint128>,
+    },
+    /// Includes a cw20 token in a full withdrawal and ExecuteSendAll
+    /// Receiving a token only records the deposit
+    TrackCw20 { token: String },
+    /// Stops a cw20 token being included in a full withdrawal
+    /// or ExecuteSendAll, e.g. if it no longer answers queries
+    /// Any balance can still be moved with ExecuteSendCw20
+    UntrackCw20 { token: String },
+    /// Sends the specified amount from the contract balance
+    /// to an address on another chain over an IBC channel
+    /// The transfer times out `timeout_seconds` after this block
+    IbcTransfer {
+        channel_id: String,
+        to_address: String,
+        amount: Coin,
+        timeout_seconds: u64,
+    },
+    /// Delegates the specified amount of native funds
+    /// held by the contract to a validator
+    Delegate { validator: String, amount: Uint128 },
+    /// Undelegates the specified amount of native funds
+    /// from a validator, back to the contract once unbonded
+    Undelegate { validator: String, amount: Uint128 },
+    /// Moves the specified amount of delegated native funds
+    /// from one validator to another
+    Redelegate {
+        src_validator: String,
+        dst_validator: String,
+        amount: Uint128,
+    },
+    /// Sets the guardians, threshold of whom must approve a withdrawal
+    /// An empty list, with a threshold of zero, removes them
+    /// Any approvals already given are cleared
+    SetGuardians {
+        guardians: Vec<String>,
+        threshold: u32,
+    },
+    /// Lifts a freeze by the emergency council
+    Unfreeze {},
+    /// Sets the emergency council, or removes it if unset
+    SetEmergencyCouncil {
+        council: Option<EmergencyCouncilMsg>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -48,15 +199,161 @@ pub enum QueryMsg {
     GetWithdrawalReadyTime {},
     /// Checks if a withdrawal is possible yet
     /// returns a bool response
-    IsWithdrawalReady {},
+    /// With beneficiaries, the beneficiary must be given
+    IsWithdrawalReady { beneficiary: Option<String> },
+    /// If the withdraw_address has nominated a successor
+    /// who has not yet accepted, this returns it
+    GetPendingWithdrawAddress {},
+    /// Returns the staking rewards the contract could claim,
+    /// summed across all of its delegations
+    GetPendingRewards {},
+    /// Returns every cw20 token the contract has received,
+    /// with the amount it currently holds
+    GetCw20Balances {},
+    /// Returns where any withdrawal is up to, and what it would send
+    /// Unlike GetWithdrawalReadyTime and IsWithdrawalReady,
+    /// this does not error when there is no withdrawal
+    /// With beneficiaries, the beneficiary must be given
+    GetWithdrawalStatus { beneficiary: Option<String> },
+    /// Returns the contract's holdings: the native denom,
+    /// any other bank denoms, and what it has staked
+    GetBalance {},
+    /// Returns the cumulative amounts withdrawn, burned,
+    /// sent by governance and sent to the community pool
+    GetTotals {},
+    /// Returns recorded actions, oldest first,
+    /// starting after the given id
+    GetActionHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the guardians who have approved the pending withdrawal,
+    /// and how many are needed
+    GetWithdrawalApprovals {},
+    /// Returns when a freeze by the emergency council lifts,
+    /// if withdrawals are frozen
+    GetFreeze {},
+    /// Returns each beneficiary with its shares, what it has claimed,
+    /// any pending withdrawal, and what it could claim now
+    GetBeneficiaries {},
+    /// Dry-runs ExecuteWithdraw as the withdraw_address,
+    /// returning the messages it would send or the error it would hit
+    /// With beneficiaries, the beneficiary to run it as must be given
+    SimulateWithdraw { beneficiary: Option<String> },
+    /// Dry-runs a sudo message, e.g. to check
+    /// a governance proposal before submitting it
+    SimulateSudo { msg: SudoMsg },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct WithdrawalTimestampResponse {
-    pub withdrawal_ready_timestamp: Timestamp,
+    pub withdrawal_ready_at: Expiration,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct WithdrawalReadyResponse {
     pub is_withdrawal_ready: bool,
 }
+
+/// Where a withdrawal is up to
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WithdrawalStatus {
+    /// No withdrawal has been started,
+    /// or the last one was claimed or cancelled
+    None,
+    /// The delay has not yet passed
+    Pending {
+        ready_at: Expiration,
+        remaining: Duration,
+    },
+    /// The withdrawal can be claimed,
+    /// until expires_at if there is a claim window
+    Ready {
+        since: Expiration,
+        expires_at: Option<Expiration>,
+    },
+    /// Governance has blocked the withdrawal
+    Vetoed { reason: String },
+    /// The claim window passed without a claim
+    Expired { expired_at: Expiration },
+    /// Withdrawals follow the vesting schedule,
+    /// amount is whatever can be claimed now
+    Vesting {},
+    /// The delay has passed, but the guardians have not yet signed off
+    AwaitingApproval { approvals: u32, threshold: u32 },
+    /// The emergency council has held every withdrawal
+    /// amount is what a claim would send once the freeze lifts
+    Frozen { until: Expiration },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WithdrawalStatusResponse {
+    pub status: WithdrawalStatus,
+    pub amount: Vec<Coin>, // what a claim would send - empty if nothing can be claimed
+    pub cw20_amount: Vec<Cw20CoinVerified>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingWithdrawAddressResponse {
+    pub pending_withdraw_address: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingRewardsResponse {
+    pub pending_rewards: Vec<Coin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ContractBalanceResponse {
+    pub native: Coin,
+    pub other: Vec<Coin>,
+    pub staked: Option<Coin>, // unset if the contract has no delegations
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Cw20BalancesResponse {
+    pub balances: Vec<Cw20CoinVerified>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ActionHistoryEntry {
+    pub id: u64,
+    pub action: ActionRecord,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ActionHistoryResponse {
+    pub actions: Vec<ActionHistoryEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulationResponse {
+    pub messages: Vec<CosmosMsg>,
+    pub error: Option<String>, // set if the message would fail, in which case nothing is sent
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BeneficiaryInfo {
+    pub address: Addr,
+    pub shares: u64,
+    pub claimed: Uint128,
+    pub withdrawal_ready: Option<Expiration>,
+    pub claimable: Uint128, // its share of the native balance, less what it has claimed
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BeneficiariesResponse {
+    pub beneficiaries: Vec<BeneficiaryInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WithdrawalApprovalsResponse {
+    pub approvals: Vec<Addr>,
+    pub threshold: Option<u32>, // unset if there are no guardians
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FreezeResponse {
+    pub frozen_until: Option<Expiration>, // unset if withdrawals are not frozen
+}
@@ -1,16 +1,258 @@
+use std::fmt;
+
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Timestamp};
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Coin, Empty, Timestamp, Uint128};
+use cw20::Cw20CoinVerified;
+use cw_storage_plus::{Item, Map};
+use cw_utils::{Duration, Expiration};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
     pub withdraw_address: Addr,
-    pub withdraw_delay_in_days: u64,
+    pub withdraw_delay: Duration,
+    pub withdraw_claim_window: Option<Duration>,
     pub native_denom: String,
+    pub vesting: Option<VestingSchedule>,
+    pub rewards_destination: RewardsDestination,
+    pub guardians: Option<GuardianSet>,
+    pub emergency_council: Option<EmergencyCouncil>,
+}
+
+/// Addresses that must approve a withdrawal before it can be claimed
+/// threshold of them are needed, and only governance can change them
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GuardianSet {
+    pub members: Vec<Addr>,
+    pub threshold: u32,
+}
+
+/// An address that can freeze withdrawals in an emergency,
+/// buying governance time to act
+/// a freeze lifts itself after freeze_duration
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EmergencyCouncil {
+    pub address: Addr,
+    pub freeze_duration: Duration,
+}
+
+/// Where staking rewards end up when they are claimed
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RewardsDestination {
+    /// Rewards stay locked in the contract
+    #[default]
+    Contract,
+    /// Rewards are sent on to the withdraw_address
+    WithdrawAddress,
+    /// Rewards are returned to the chain's community pool
+    CommunityPool,
+}
+
+impl fmt::Display for RewardsDestination {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RewardsDestination::Contract => write!(f, "contract"),
+            RewardsDestination::WithdrawAddress => write!(f, "withdraw_address"),
+            RewardsDestination::CommunityPool => write!(f, "community_pool"),
+        }
+    }
+}
+
+/// A linear release of the native denom
+/// nothing can be claimed before the cliff,
+/// and everything can be claimed after the end
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VestingSchedule {
+    pub start_time: Timestamp,
+    pub cliff_time: Timestamp,
+    pub end_time: Timestamp,
+}
+
+impl VestingSchedule {
+    /// start <= cliff <= end, and the schedule must have some length
+    pub fn is_valid(&self) -> bool {
+        self.start_time <= self.cliff_time
+            && self.cliff_time <= self.end_time
+            && self.start_time < self.end_time
+    }
+
+    /// How much of `total` has vested at `now`
+    pub fn vested_amount(&self, now: Timestamp, total: Uint128) -> Uint128 {
+        if now < self.cliff_time {
+            return Uint128::zero();
+        }
+        if now >= self.end_time {
+            return total;
+        }
+
+        let elapsed = now.seconds() - self.start_time.seconds();
+        let duration = self.end_time.seconds() - self.start_time.seconds();
+
+        total.multiply_ratio(elapsed, duration)
+    }
+}
+
+/// One of several addresses sharing the native balance
+/// each withdraws its own pro-rata share, on its own timer
+/// veto is set if governance has blocked the pending withdrawal
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Beneficiary {
+    pub shares: u64,
+    pub claimed: Uint128,
+    pub withdrawal_ready: Option<Expiration>,
+    pub veto: Option<String>,
+}
+
+/// The kinds of action recorded in the action history
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionKind {
+    StartWithdraw,
+    ExecuteWithdraw,
+    CancelWithdraw,
+    ProposeNewWithdrawAddress,
+    AcceptWithdrawAddress,
+    ClaimRewards,
+    ReceiveCw20,
+    Burn,
+    Send,
+    SendAll,
+    SplitSend,
+    SendCw20,
+    BurnCw20,
+    TrackCw20,
+    UntrackCw20,
+    IbcTransfer,
+    FundCommunityPool,
+    VetoWithdrawal,
+    ApproveWithdrawal,
+    SetGuardians,
+    Freeze,
+    Unfreeze,
+    SetEmergencyCouncil,
+    UpdateConfig,
+    Delegate,
+    Undelegate,
+    Redelegate,
+}
+
+/// An entry in the action history
+/// actor is unset where governance took the action
+/// a cancel or veto records whose withdrawal it was in recipient,
+/// and tracking a cw20 token records it with a zero cw20_amount
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ActionRecord {
+    pub kind: ActionKind,
+    pub actor: Option<Addr>,
+    pub amount: Vec<Coin>,
+    pub cw20_amount: Vec<Cw20CoinVerified>,
+    pub recipient: Option<String>,
+    pub time: Timestamp,
+}
+
+impl ActionRecord {
+    /// A record with no actor, amounts or recipient
+    pub fn new(kind: ActionKind, time: Timestamp) -> Self {
+        ActionRecord {
+            kind,
+            actor: None,
+            amount: vec![],
+            cw20_amount: vec![],
+            recipient: None,
+            time,
+        }
+    }
+}
+
+/// Cumulative amounts that have left the contract, per denom
+/// staking rewards and cw20 tokens are not included
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct Totals {
+    pub withdrawn: Vec<Coin>,
+    pub burned: Vec<Coin>,
+    pub sent: Vec<Coin>,
+    pub sent_to_community_pool: Vec<Coin>,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
 
-pub const WITHDRAWAL_READY: Item<Timestamp> = Item::new("withdrawal_ready");
+// in the same unit, height or time, as the withdraw delay
+pub const WITHDRAWAL_READY: Item<Expiration> = Item::new("withdrawal_ready");
+
+// if set, the pending withdrawal is only for this amount
+// rather than for everything held by the contract
+pub const WITHDRAWAL_AMOUNT: Item<Vec<Coin>> = Item::new("withdrawal_amount");
+
+// if set, governance has vetoed the pending withdrawal
+// and this is the reason it gave
+pub const WITHDRAWAL_VETO: Item<String> = Item::new("withdrawal_veto");
+
+// guardians who have approved the pending withdrawal
+pub const WITHDRAWAL_APPROVALS: Item<Vec<Addr>> = Item::new("withdrawal_approvals");
+
+// if set, withdrawals are frozen until this point
+// unless governance unfreezes them sooner
+pub const FROZEN_UNTIL: Item<Expiration> = Item::new("frozen_until");
+
+// the emergency council cannot freeze again until this point,
+// one freeze duration after the last freeze was due to end
+pub const FREEZE_COOLDOWN: Item<Expiration> = Item::new("freeze_cooldown");
+
+// a successor nominated by the withdraw_address
+// who has yet to accept the handover
+pub const PENDING_WITHDRAW_ADDRESS: Item<Addr> = Item::new("pending_withdraw_address");
+
+// running total of native denom claimed under the vesting schedule
+pub const VESTING_CLAIMED: Item<Uint128> = Item::new("vesting_claimed");
+
+// if any are set, withdrawals are split between these
+// rather than going to the withdraw_address
+pub const BENEFICIARIES: Map<&Addr, Beneficiary> = Map::new("beneficiaries");
+
+// running total of native denom claimed by all beneficiaries
+pub const BENEFICIARIES_CLAIMED: Item<Uint128> = Item::new("beneficiaries_claimed");
+
+// cw20 contracts that have sent tokens to this contract
+// balances are always read from the token contract itself
+pub const CW20_TOKENS: Map<&Addr, Empty> = Map::new("cw20_tokens");
+
+// running totals of funds out, see Totals
+pub const TOTALS: Item<Totals> = Item::new("totals");
+
+// append-only, keyed by a sequential id
+pub const ACTION_HISTORY: Map<u64, ActionRecord> = Map::new("action_history");
+
+// the number of actions recorded, i.e. the next id
+pub const ACTION_COUNT: Item<u64> = Item::new("action_count");
+
+/// Config as stored by v0.3.x of the contract
+/// only used to read state when migrating
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigV0_3 {
+    pub withdraw_address: Addr,
+    pub withdraw_delay_in_days: u64,
+    pub native_denom: String,
+}
+
+pub const CONFIG_V0_3: Item<ConfigV0_3> = Item::new("config");
+
+/// Config as stored by v0.4.x of the contract
+/// only used to read state when migrating
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigV0_4 {
+    pub withdraw_address: Addr,
+    pub withdraw_delay_in_days: u64,
+    pub withdraw_claim_window_in_days: Option<u64>,
+    pub native_denom: String,
+    pub vesting: Option<VestingSchedule>,
+    // early v0.4.0 builds predate rewards_destination
+    #[serde(default)]
+    pub rewards_destination: RewardsDestination,
+}
+
+pub const CONFIG_V0_4: Item<ConfigV0_4> = Item::new("config");
+
+// up to v0.4.x the ready time was always a timestamp
+pub const WITHDRAWAL_READY_V0_4: Item<Timestamp> = Item::new("withdrawal_ready");
@@ -2,15 +2,27 @@
 mod tests {
     use crate::contract::{execute, instantiate, query, sudo};
     use crate::msg::{
-        ExecuteMsg, InstantiateMsg, QueryMsg, SudoMsg, WithdrawalReadyResponse,
-        WithdrawalTimestampResponse,
+        ActionHistoryEntry, ActionHistoryResponse, BeneficiariesResponse, BeneficiaryInfo,
+        BeneficiaryShare, ContractBalanceResponse, Cw20BalancesResponse, EmergencyCouncilMsg,
+        ExecuteMsg, FreezeResponse, InstantiateMsg, PendingRewardsResponse,
+        PendingWithdrawAddressResponse, QueryMsg, SimulationResponse, SudoMsg,
+        WithdrawalApprovalsResponse, WithdrawalReadyResponse, WithdrawalStatus,
+        WithdrawalStatusResponse, WithdrawalTimestampResponse,
+    };
+    use crate::state::{
+        ActionKind, ActionRecord, Config, RewardsDestination, Totals, VestingSchedule,
     };
-    use crate::state::Config;
     use crate::ContractError;
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
     use cosmwasm_std::{
-        coins, from_binary, Addr, BankMsg, CosmosMsg, Response, StdError, Timestamp, Uint128,
+        coin, coins, from_json, to_json_binary, Addr, BankMsg, Binary, ContractResult, CosmosMsg,
+        Decimal, DistributionMsg, FullDelegation, IbcMsg, IbcTimeout, QuerierResult, Response,
+        StakingMsg, StdError, SystemError, SystemResult, Timestamp, Uint128, Validator, WasmQuery,
+    };
+    use cw20::{
+        BalanceResponse, Cw20CoinVerified, Cw20QueryMsg, Cw20ReceiveMsg, TokenInfoResponse,
     };
+    use cw_utils::{Duration, Expiration, DAY};
 
     const NATIVE_DENOM: &str = "ujuno";
 
@@ -20,12 +32,17 @@ mod tests {
 
         let withdraw_address = String::from("gordon-gekko-address"); // in reality this would be e.g. juno16g2rahf5846rxzp3fwlswy08fz8ccuwk03k57y
         let validated_addr = Addr::unchecked(&withdraw_address);
-        let withdraw_delay_in_days = 28; // this is what we are expecting to set it to
+        let withdraw_delay = DAY * 28; // this is what we are expecting to set it to
 
         let msg = InstantiateMsg {
             withdraw_address,
-            withdraw_delay_in_days,
+            withdraw_delay,
+            withdraw_claim_window: None,
             native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
         };
 
         // the person instantiating
@@ -37,12 +54,17 @@ mod tests {
 
         // query state
         let res = query(deps.as_ref(), mock_env(), QueryMsg::GetConfig {}).unwrap();
-        let contract_config: Config = from_binary(&res).unwrap();
+        let contract_config: Config = from_json(&res).unwrap();
         assert_eq!(
             Config {
                 withdraw_address: validated_addr,
-                withdraw_delay_in_days,
+                withdraw_delay,
+                withdraw_claim_window: None,
                 native_denom: NATIVE_DENOM.to_string(),
+                vesting: None,
+                rewards_destination: RewardsDestination::Contract,
+                guardians: None,
+                emergency_council: None,
             },
             contract_config
         );
@@ -59,12 +81,17 @@ mod tests {
 
         let withdraw_address = String::from("gordon-gekko-address"); // in reality this would be e.g. juno16g2rahf5846rxzp3fwlswy08fz8ccuwk03k57y
         let _validated_addr = Addr::unchecked(&withdraw_address);
-        let withdraw_delay_in_days = 28; // this is what we are expecting to set it to
+        let withdraw_delay = DAY * 28; // this is what we are expecting to set it to
 
         let msg = InstantiateMsg {
             withdraw_address,
-            withdraw_delay_in_days,
+            withdraw_delay,
+            withdraw_claim_window: None,
             native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
         };
 
         // the person instantiating
@@ -80,7 +107,7 @@ mod tests {
             .update_balance(&contract_addr, funds_sent_to_contract.clone());
 
         // mock sudo_msg
-        let msg = SudoMsg::ExecuteBurn {};
+        let msg = SudoMsg::ExecuteBurn { amount: None };
         let res = sudo(deps.as_mut(), env, msg).unwrap();
 
         // cosmos msgs we expect
@@ -94,10 +121,86 @@ mod tests {
             Response::new()
                 .add_attribute("message_type", "sudo")
                 .add_attribute("action", "burn")
+                .add_attribute("amount", "1000000ujuno")
                 .add_messages(msgs)
         );
     }
 
+    #[test]
+    fn sudo_burn_partial() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let msg = InstantiateMsg {
+            withdraw_address: String::from("gordon-gekko-address"),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &[]);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // mock native and ibc funds being added to contract
+        let contract_addr = env.clone().contract.address;
+        deps.querier.update_balance(
+            &contract_addr,
+            vec![coin(1_000_000, NATIVE_DENOM), coin(500, "uatom")],
+        );
+
+        // burn half the native pot, keep the ibc tokens
+        let msg = SudoMsg::ExecuteBurn {
+            amount: Some(coins(500_000, NATIVE_DENOM)),
+        };
+        let res = sudo(deps.as_mut(), env.clone(), msg).unwrap();
+
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("message_type", "sudo")
+                .add_attribute("action", "burn")
+                .add_attribute("amount", "500000ujuno")
+                .add_message(BankMsg::Burn {
+                    amount: coins(500_000, NATIVE_DENOM),
+                })
+        );
+
+        // can't burn more than the contract holds of a denom
+        let msg = SudoMsg::ExecuteBurn {
+            amount: Some(vec![coin(500_000, NATIVE_DENOM), coin(501, "uatom")]),
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientContractBalance {});
+
+        // nor a denom it doesn't hold at all
+        let msg = SudoMsg::ExecuteBurn {
+            amount: Some(coins(1, "uosmo")),
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientContractBalance {});
+
+        // and a zero burn is refused
+        let msg = SudoMsg::ExecuteBurn {
+            amount: Some(coins(0, NATIVE_DENOM)),
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidAmount {});
+
+        // as is a denom named twice, even if each would fit
+        let msg = SudoMsg::ExecuteBurn {
+            amount: Some(vec![
+                coin(600_000, NATIVE_DENOM),
+                coin(600_000, NATIVE_DENOM),
+            ]),
+        };
+        let err = sudo(deps.as_mut(), env, msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidAmount {});
+    }
+
     #[test]
     fn sudo_send() {
         let mut deps = mock_dependencies();
@@ -109,15 +212,20 @@ mod tests {
 
         let withdraw_address = String::from("gordon-gekko-address"); // in reality this would be e.g. juno16g2rahf5846rxzp3fwlswy08fz8ccuwk03k57y
         let _validated_addr = Addr::unchecked(&withdraw_address);
-        let withdraw_delay_in_days = 28; // this is what we are expecting to set it to
+        let withdraw_delay = DAY * 28; // this is what we are expecting to set it to
 
         // look I get that this is an increasingly niche joke
         let community_nominated_address = String::from("carl-fox-address");
 
         let msg = InstantiateMsg {
             withdraw_address,
-            withdraw_delay_in_days,
+            withdraw_delay,
+            withdraw_claim_window: None,
             native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
         };
 
         // the person instantiating
@@ -138,6 +246,7 @@ mod tests {
         let msg = SudoMsg::ExecuteSend {
             recipient: community_nominated_address.clone(),
             amount: Uint128::new(3_000_000), // spell it out explicitly
+            denom: None,
         };
         let res = sudo(deps.as_mut(), env, msg).unwrap();
 
@@ -168,15 +277,20 @@ mod tests {
 
         let withdraw_address = String::from("gordon-gekko-address"); // in reality this would be e.g. juno16g2rahf5846rxzp3fwlswy08fz8ccuwk03k57y
         let _validated_addr = Addr::unchecked(&withdraw_address);
-        let withdraw_delay_in_days = 28; // this is what we are expecting to set it to
+        let withdraw_delay = DAY * 28; // this is what we are expecting to set it to
 
         // look I get that this is an increasingly niche joke
         let community_nominated_address = String::from("carl-fox-address");
 
         let msg = InstantiateMsg {
             withdraw_address,
-            withdraw_delay_in_days,
+            withdraw_delay,
+            withdraw_claim_window: None,
             native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
         };
 
         // the person instantiating
@@ -225,15 +339,20 @@ mod tests {
 
         let withdraw_address = String::from("gordon-gekko-address"); // in reality this would be e.g. juno16g2rahf5846rxzp3fwlswy08fz8ccuwk03k57y
         let _validated_addr = Addr::unchecked(&withdraw_address);
-        let withdraw_delay_in_days = 28; // this is what we are expecting to set it to
+        let withdraw_delay = DAY * 28; // this is what we are expecting to set it to
 
         // look I get that this is an increasingly niche joke
         let community_nominated_address = String::from("carl-fox-address");
 
         let msg = InstantiateMsg {
             withdraw_address,
-            withdraw_delay_in_days,
+            withdraw_delay,
+            withdraw_claim_window: None,
             native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
         };
 
         // the person instantiating
@@ -253,10 +372,17 @@ mod tests {
         let msg = SudoMsg::ExecuteSend {
             recipient: community_nominated_address,
             amount: Uint128::new(5_000_000), // spell it out explicitly
+            denom: None,
         };
         let err = sudo(deps.as_mut(), env, msg).unwrap_err();
 
-        assert_eq!(err, ContractError::InsufficientContractBalance {});
+        assert_eq!(
+            err,
+            ContractError::InsufficientDenomBalance {
+                denom: NATIVE_DENOM.to_string(),
+                shortfall: Uint128::new(2_000_000),
+            }
+        );
     }
 
     #[test]
@@ -268,15 +394,20 @@ mod tests {
 
         let withdraw_address = String::from("gordon-gekko-address"); // in reality this would be e.g. juno16g2rahf5846rxzp3fwlswy08fz8ccuwk03k57y
         let _validated_addr = Addr::unchecked(&withdraw_address);
-        let withdraw_delay_in_days = 28; // this is what we are expecting to set it to
+        let withdraw_delay = DAY * 28; // this is what we are expecting to set it to
 
         // look I get that this is an increasingly niche joke
         let community_nominated_address = String::from("carl-fox-address");
 
         let msg = InstantiateMsg {
             withdraw_address,
-            withdraw_delay_in_days,
+            withdraw_delay,
+            withdraw_claim_window: None,
             native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
         };
 
         // the person instantiating
@@ -291,10 +422,17 @@ mod tests {
         let msg = SudoMsg::ExecuteSend {
             recipient: community_nominated_address,
             amount: Uint128::new(5_000_000), // spell it out explicitly
+            denom: None,
         };
         let err = sudo(deps.as_mut(), env, msg).unwrap_err();
 
-        assert_eq!(err, ContractError::NoNativeBalance {});
+        assert_eq!(
+            err,
+            ContractError::InsufficientDenomBalance {
+                denom: NATIVE_DENOM.to_string(),
+                shortfall: Uint128::new(5_000_000),
+            }
+        );
     }
 
     #[test]
@@ -308,12 +446,17 @@ mod tests {
 
         let withdraw_address = String::from("gordon-gekko-address"); // in reality this would be e.g. juno16g2rahf5846rxzp3fwlswy08fz8ccuwk03k57y
         let _validated_addr = Addr::unchecked(&withdraw_address);
-        let withdraw_delay_in_days = 28; // this is what we are expecting to set it to
+        let withdraw_delay = DAY * 28; // this is what we are expecting to set it to
 
         let msg = InstantiateMsg {
             withdraw_address,
-            withdraw_delay_in_days,
+            withdraw_delay,
+            withdraw_claim_window: None,
             native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
         };
 
         // the person instantiating
@@ -344,7 +487,12 @@ mod tests {
 
         // withdraw should return err
         // is the withdrawal ready?
-        let sense_check = query(deps.as_ref(), env, QueryMsg::IsWithdrawalReady {}).unwrap_err();
+        let sense_check = query(
+            deps.as_ref(),
+            env,
+            QueryMsg::IsWithdrawalReady { beneficiary: None },
+        )
+        .unwrap_err();
         assert_eq!(
             StdError::NotFound {
                 kind: "Withdrawal not yet requested - no Withdrawal time exists".to_string()
@@ -364,12 +512,17 @@ mod tests {
 
         let withdraw_address = String::from("gordon-gekko-address"); // in reality this would be e.g. juno16g2rahf5846rxzp3fwlswy08fz8ccuwk03k57y
         let _validated_addr = Addr::unchecked(&withdraw_address);
-        let withdraw_delay_in_days = 28; // this is what we are expecting to set it to
+        let withdraw_delay = DAY * 28; // this is what we are expecting to set it to
 
         let msg = InstantiateMsg {
             withdraw_address: withdraw_address.clone(),
-            withdraw_delay_in_days,
+            withdraw_delay,
+            withdraw_claim_window: None,
             native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
         };
 
         // the person instantiating
@@ -386,12 +539,17 @@ mod tests {
 
         // only withdraw_address can call
         let info = mock_info(&withdraw_address, &[]);
-        let msg = ExecuteMsg::StartWithdraw {};
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
         let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
         // is the withdrawal ready?
-        let is_ready: WithdrawalReadyResponse = from_binary(
-            &query(deps.as_ref(), env.clone(), QueryMsg::IsWithdrawalReady {}).unwrap(),
+        let is_ready: WithdrawalReadyResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::IsWithdrawalReady { beneficiary: None },
+            )
+            .unwrap(),
         )
         .unwrap();
 
@@ -404,7 +562,7 @@ mod tests {
 
         // query timestamp
         let res = query(deps.as_ref(), env, QueryMsg::GetWithdrawalReadyTime {}).unwrap();
-        let value: WithdrawalTimestampResponse = from_binary(&res).unwrap();
+        let value: WithdrawalTimestampResponse = from_json(&res).unwrap();
 
         // 28 days time from 'now', where 'now' is zero
         let delay_in_seconds = 28u64 * 86400u64;
@@ -413,7 +571,7 @@ mod tests {
 
         assert_eq!(
             WithdrawalTimestampResponse {
-                withdrawal_ready_timestamp: twenty_eight_days_from_now_timestamp,
+                withdrawal_ready_at: Expiration::AtTime(twenty_eight_days_from_now_timestamp),
             },
             value
         );
@@ -430,12 +588,17 @@ mod tests {
 
         let withdraw_address = String::from("gordon-gekko-address"); // in reality this would be e.g. juno16g2rahf5846rxzp3fwlswy08fz8ccuwk03k57y
         let _validated_addr = Addr::unchecked(&withdraw_address);
-        let withdraw_delay_in_days = 28; // this is what we are expecting to set it to
+        let withdraw_delay = DAY * 28; // this is what we are expecting to set it to
 
         let msg = InstantiateMsg {
             withdraw_address: withdraw_address.clone(),
-            withdraw_delay_in_days,
+            withdraw_delay,
+            withdraw_claim_window: None,
             native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
         };
 
         // the person instantiating
@@ -452,13 +615,13 @@ mod tests {
 
         // random address can't call
         let random = mock_info("some-random-guy", &[]);
-        let msg = ExecuteMsg::StartWithdraw {};
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
         let err = execute(deps.as_mut(), env.clone(), random, msg).unwrap_err();
         assert_eq!(err, ContractError::Unauthorized {});
 
         // only withdraw_address can call
         let info = mock_info(&withdraw_address, &[]);
-        let msg = ExecuteMsg::StartWithdraw {};
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
         let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
         // 28 days time from 'now', where 'now' is zero
@@ -470,9 +633,15 @@ mod tests {
         env.block.time = twenty_eight_days_from_now_timestamp.plus_seconds(3600);
 
         // is the withdrawal ready?
-        let is_ready: WithdrawalReadyResponse =
-            from_binary(&query(deps.as_ref(), env, QueryMsg::IsWithdrawalReady {}).unwrap())
-                .unwrap();
+        let is_ready: WithdrawalReadyResponse = from_json(
+            query(
+                deps.as_ref(),
+                env,
+                QueryMsg::IsWithdrawalReady { beneficiary: None },
+            )
+            .unwrap(),
+        )
+        .unwrap();
 
         assert_eq!(
             WithdrawalReadyResponse {
@@ -493,12 +662,17 @@ mod tests {
 
         let withdraw_address = String::from("gordon-gekko-address"); // in reality this would be e.g. juno16g2rahf5846rxzp3fwlswy08fz8ccuwk03k57y
         let _validated_addr = Addr::unchecked(&withdraw_address);
-        let withdraw_delay_in_days = 28; // this is what we are expecting to set it to
+        let withdraw_delay = DAY * 28; // this is what we are expecting to set it to
 
         let msg = InstantiateMsg {
             withdraw_address: withdraw_address.clone(),
-            withdraw_delay_in_days,
+            withdraw_delay,
+            withdraw_claim_window: None,
             native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
         };
 
         // the person instantiating
@@ -515,7 +689,7 @@ mod tests {
 
         // only withdraw_address can call
         let info = mock_info(&withdraw_address, &[]);
-        let msg = ExecuteMsg::StartWithdraw {};
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
         let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
         // 28 days time from 'now', where 'now' is zero
@@ -527,8 +701,13 @@ mod tests {
         env.block.time = twenty_eight_days_from_now_timestamp.plus_seconds(3600);
 
         // is the withdrawal ready?
-        let is_ready: WithdrawalReadyResponse = from_binary(
-            &query(deps.as_ref(), env.clone(), QueryMsg::IsWithdrawalReady {}).unwrap(),
+        let is_ready: WithdrawalReadyResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::IsWithdrawalReady { beneficiary: None },
+            )
+            .unwrap(),
         )
         .unwrap();
 
@@ -576,12 +755,17 @@ mod tests {
 
         let withdraw_address = String::from("gordon-gekko-address"); // in reality this would be e.g. juno16g2rahf5846rxzp3fwlswy08fz8ccuwk03k57y
         let _validated_addr = Addr::unchecked(&withdraw_address);
-        let withdraw_delay_in_days = 28; // this is what we are expecting to set it to
+        let withdraw_delay = DAY * 28; // this is what we are expecting to set it to
 
         let msg = InstantiateMsg {
             withdraw_address: withdraw_address.clone(),
-            withdraw_delay_in_days,
+            withdraw_delay,
+            withdraw_claim_window: None,
             native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
         };
 
         // the person instantiating
@@ -598,7 +782,7 @@ mod tests {
 
         // only withdraw_address can call
         let info = mock_info(&withdraw_address, &[]);
-        let msg = ExecuteMsg::StartWithdraw {};
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
         let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
         // 28 days time from 'now', where 'now' is zero
@@ -610,9 +794,15 @@ mod tests {
         env.block.time = twenty_eight_days_from_now_timestamp.minus_seconds(3600);
 
         // is the withdrawal ready?
-        let is_ready: WithdrawalReadyResponse =
-            from_binary(&query(deps.as_ref(), env, QueryMsg::IsWithdrawalReady {}).unwrap())
-                .unwrap();
+        let is_ready: WithdrawalReadyResponse = from_json(
+            query(
+                deps.as_ref(),
+                env,
+                QueryMsg::IsWithdrawalReady { beneficiary: None },
+            )
+            .unwrap(),
+        )
+        .unwrap();
 
         assert_eq!(
             WithdrawalReadyResponse {
@@ -621,4 +811,3177 @@ mod tests {
             is_ready
         );
     }
+
+    #[test]
+    fn vesting_invalid_schedule_fails() {
+        let mut deps = mock_dependencies();
+
+        let withdraw_address = String::from("gordon-gekko-address");
+
+        // cliff is after the end
+        let msg = InstantiateMsg {
+            withdraw_address,
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: Some(VestingSchedule {
+                start_time: Timestamp::from_seconds(0),
+                cliff_time: Timestamp::from_seconds(2_000),
+                end_time: Timestamp::from_seconds(1_000),
+            }),
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &[]);
+        let err = instantiate(deps.as_mut(), mock_env(), instantiate_info, msg).unwrap_err();
+
+        assert_eq!(err, ContractError::InvalidVestingSchedule {});
+    }
+
+    #[test]
+    fn vesting_claims_linearly() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        env.block.time = Timestamp::from_seconds(0);
+
+        let funds_sent_to_contract = coins(1_000_000, NATIVE_DENOM);
+
+        let withdraw_address = String::from("gordon-gekko-address");
+
+        // vests over 1000s, with nothing until 250s
+        let msg = InstantiateMsg {
+            withdraw_address: withdraw_address.clone(),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: Some(VestingSchedule {
+                start_time: Timestamp::from_seconds(0),
+                cliff_time: Timestamp::from_seconds(250),
+                end_time: Timestamp::from_seconds(1_000),
+            }),
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &funds_sent_to_contract);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // mock funds being added to contract
+        let contract_addr = env.clone().contract.address;
+        deps.querier
+            .update_balance(&contract_addr, funds_sent_to_contract);
+
+        let info = mock_info(&withdraw_address, &[]);
+
+        // there is no single withdrawal for guardians to approve
+        let msg = SudoMsg::SetGuardians {
+            guardians: vec![String::from("lou-mannheim-address")],
+            threshold: 1,
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::GuardiansUnsupported {});
+
+        // there is no timer to start under vesting
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::StartWithdraw { amount: None },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::VestingScheduleActive {});
+
+        // before the cliff nothing is claimable
+        env.block.time = Timestamp::from_seconds(100);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::ExecuteWithdraw {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NothingVested {});
+
+        // halfway through, half is claimable
+        env.block.time = Timestamp::from_seconds(500);
+        let is_ready: WithdrawalReadyResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::IsWithdrawalReady { beneficiary: None },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            WithdrawalReadyResponse {
+                is_withdrawal_ready: true,
+            },
+            is_ready
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::ExecuteWithdraw {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: withdraw_address.clone(),
+                amount: coins(500_000, NATIVE_DENOM),
+            })
+        );
+
+        // mock the send having happened
+        deps.querier
+            .update_balance(&contract_addr, coins(500_000, NATIVE_DENOM));
+
+        // claiming again in the same block gets nothing
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::ExecuteWithdraw {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NothingVested {});
+
+        // after the end, the remainder is claimable
+        env.block.time = Timestamp::from_seconds(1_500);
+        let res = execute(deps.as_mut(), env, info, ExecuteMsg::ExecuteWithdraw {}).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: withdraw_address,
+                amount: coins(500_000, NATIVE_DENOM),
+            })
+        );
+    }
+
+    #[test]
+    fn claim_partial_withdrawal() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        env.block.time = Timestamp::from_seconds(0);
+
+        let funds_sent_to_contract = coins(1_000_000, NATIVE_DENOM);
+
+        let withdraw_address = String::from("gordon-gekko-address");
+
+        let msg = InstantiateMsg {
+            withdraw_address: withdraw_address.clone(),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &funds_sent_to_contract);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // mock funds being added to contract
+        let contract_addr = env.clone().contract.address;
+        deps.querier
+            .update_balance(&contract_addr, funds_sent_to_contract);
+
+        let info = mock_info(&withdraw_address, &[]);
+
+        // asking for nothing is not a partial withdrawal
+        let msg = ExecuteMsg::StartWithdraw {
+            amount: Some(coins(0, NATIVE_DENOM)),
+        };
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidAmount {});
+
+        // nor is naming a denom twice
+        let msg = ExecuteMsg::StartWithdraw {
+            amount: Some(vec![
+                coin(600_000, NATIVE_DENOM),
+                coin(600_000, NATIVE_DENOM),
+            ]),
+        };
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidAmount {});
+
+        // only ask for some of the funds
+        let msg = ExecuteMsg::StartWithdraw {
+            amount: Some(coins(400_000, NATIVE_DENOM)),
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // roll time forward in env to 1 hr after the 28 day delay
+        env.block.time = Timestamp::from_seconds(28 * 86400).plus_seconds(3600);
+
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // only the requested amount is sent
+        let msgs: Vec<CosmosMsg> = vec![BankMsg::Send {
+            to_address: withdraw_address.clone(),
+            amount: coins(400_000, NATIVE_DENOM),
+        }
+        .into()];
+
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "execute_withdraw")
+                .add_attribute("withdraw_address", withdraw_address)
+                .add_messages(msgs)
+        );
+
+        // the request has been used up
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        execute(deps.as_mut(), env, info, msg).unwrap_err();
+    }
+
+    #[test]
+    fn claim_partial_withdrawal_balance_too_small_fails() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        env.block.time = Timestamp::from_seconds(0);
+
+        let funds_sent_to_contract = coins(1_000_000, NATIVE_DENOM);
+
+        let withdraw_address = String::from("gordon-gekko-address");
+
+        let msg = InstantiateMsg {
+            withdraw_address: withdraw_address.clone(),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &funds_sent_to_contract);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // mock funds being added to contract
+        let contract_addr = env.clone().contract.address;
+        deps.querier
+            .update_balance(&contract_addr, funds_sent_to_contract);
+
+        let info = mock_info(&withdraw_address, &[]);
+        let msg = ExecuteMsg::StartWithdraw {
+            amount: Some(coins(800_000, NATIVE_DENOM)),
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // governance sends some funds elsewhere in the meantime
+        deps.querier
+            .update_balance(&contract_addr, coins(500_000, NATIVE_DENOM));
+
+        env.block.time = Timestamp::from_seconds(28 * 86400).plus_seconds(3600);
+
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientContractBalance {});
+    }
+
+    #[test]
+    fn cancel_withdrawal() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        env.block.time = Timestamp::from_seconds(0);
+
+        let withdraw_address = String::from("gordon-gekko-address");
+
+        let msg = InstantiateMsg {
+            withdraw_address: withdraw_address.clone(),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &[]);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        let info = mock_info(&withdraw_address, &[]);
+
+        // nothing to cancel yet
+        let msg = ExecuteMsg::CancelWithdraw {};
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::NoPendingWithdrawal {});
+
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // random address can't cancel
+        let random = mock_info("some-random-guy", &[]);
+        let msg = ExecuteMsg::CancelWithdraw {};
+        let err = execute(deps.as_mut(), env.clone(), random, msg).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let msg = ExecuteMsg::CancelWithdraw {};
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "cancel_withdraw")
+                .add_attribute("withdraw_address", withdraw_address)
+        );
+
+        // the timestamp is gone
+        let err = query(deps.as_ref(), env, QueryMsg::GetWithdrawalReadyTime {}).unwrap_err();
+        assert_eq!(
+            StdError::NotFound {
+                kind: "Withdrawal not yet requested - no Withdrawal time exists".to_string()
+            },
+            err
+        );
+    }
+
+    #[test]
+    fn sudo_cancel_withdrawal() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        env.block.time = Timestamp::from_seconds(0);
+
+        let withdraw_address = String::from("gordon-gekko-address");
+
+        let msg = InstantiateMsg {
+            withdraw_address: withdraw_address.clone(),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &[]);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        let info = mock_info(&withdraw_address, &[]);
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let res = sudo(
+            deps.as_mut(),
+            env.clone(),
+            SudoMsg::CancelWithdraw { beneficiary: None },
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("message_type", "sudo")
+                .add_attribute("action", "cancel_withdraw")
+        );
+
+        // and there is nothing left to cancel
+        let err = sudo(
+            deps.as_mut(),
+            env,
+            SudoMsg::CancelWithdraw { beneficiary: None },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NoPendingWithdrawal {});
+    }
+
+    #[test]
+    fn sudo_veto_withdrawal() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        env.block.time = Timestamp::from_seconds(0);
+
+        let funds_sent_to_contract = coins(1_000_000, NATIVE_DENOM);
+
+        let withdraw_address = String::from("gordon-gekko-address");
+
+        let msg = InstantiateMsg {
+            withdraw_address: withdraw_address.clone(),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &funds_sent_to_contract);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // mock funds being added to contract
+        let contract_addr = env.clone().contract.address;
+        deps.querier
+            .update_balance(&contract_addr, funds_sent_to_contract);
+
+        // nothing to veto yet
+        let msg = SudoMsg::VetoWithdrawal {
+            reason: String::from("greed is not good"),
+            beneficiary: None,
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::NoPendingWithdrawal {});
+
+        let info = mock_info(&withdraw_address, &[]);
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let msg = SudoMsg::VetoWithdrawal {
+            reason: String::from("greed is not good"),
+            beneficiary: None,
+        };
+        let res = sudo(deps.as_mut(), env.clone(), msg).unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("message_type", "sudo")
+                .add_attribute("action", "veto_withdrawal")
+                .add_attribute("reason", "greed is not good")
+        );
+
+        // roll time forward in env to 1 hr after the 28 day delay
+        env.block.time = Timestamp::from_seconds(28 * 86400).plus_seconds(3600);
+
+        // is the withdrawal ready?
+        let is_ready: WithdrawalReadyResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::IsWithdrawalReady { beneficiary: None },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            WithdrawalReadyResponse {
+                is_withdrawal_ready: false,
+            },
+            is_ready
+        );
+
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::WithdrawalVetoed {
+                reason: String::from("greed is not good")
+            }
+        );
+
+        // a fresh request is not vetoed
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        env.block.time = env.block.time.plus_seconds(28 * 86400 + 3600);
+
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        execute(deps.as_mut(), env, info, msg).unwrap();
+    }
+
+    #[test]
+    fn claim_window_must_be_positive() {
+        let mut deps = mock_dependencies();
+
+        let msg = InstantiateMsg {
+            withdraw_address: String::from("gordon-gekko-address"),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: Some(Duration::Time(0)),
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &[]);
+        let err = instantiate(deps.as_mut(), mock_env(), instantiate_info, msg).unwrap_err();
+
+        assert_eq!(err, ContractError::InvalidClaimWindow {});
+
+        // nor can blocks be added to a time
+        let msg = InstantiateMsg {
+            withdraw_address: String::from("gordon-gekko-address"),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: Some(Duration::Height(1_000)),
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &[]);
+        let err = instantiate(deps.as_mut(), mock_env(), instantiate_info, msg).unwrap_err();
+
+        assert_eq!(err, ContractError::InvalidClaimWindow {});
+    }
+
+    #[test]
+    fn withdraw_delay_in_blocks() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let funds_sent_to_contract = coins(1_000_000, NATIVE_DENOM);
+
+        let withdraw_address = String::from("gordon-gekko-address");
+
+        // 100 blocks to wait, then 50 blocks to claim
+        let msg = InstantiateMsg {
+            withdraw_address: withdraw_address.clone(),
+            withdraw_delay: Duration::Height(100),
+            withdraw_claim_window: Some(Duration::Height(50)),
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &funds_sent_to_contract);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // mock funds being added to contract
+        let contract_addr = env.clone().contract.address;
+        deps.querier
+            .update_balance(&contract_addr, funds_sent_to_contract.clone());
+
+        let info = mock_info(&withdraw_address, &[]);
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let ready_at = Expiration::AtHeight(env.block.height + 100);
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::GetWithdrawalStatus { beneficiary: None },
+        )
+        .unwrap();
+        let value: WithdrawalStatusResponse = from_json(&res).unwrap();
+        assert_eq!(
+            value.status,
+            WithdrawalStatus::Pending {
+                ready_at,
+                remaining: Duration::Height(100),
+            }
+        );
+
+        // a long time passes, but only up to the ready block,
+        // and the delay has to have passed
+        env.block.time = env.block.time.plus_seconds(365 * 86400);
+        env.block.height += 100;
+
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::WithdrawalNotReady {});
+
+        // and one more block is enough
+        env.block.height += 1;
+
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        let res = execute(deps.as_mut(), env, info, msg).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: withdraw_address,
+                amount: funds_sent_to_contract,
+            })
+        );
+    }
+
+    #[test]
+    fn withdraw_delay_boundary() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        env.block.time = Timestamp::from_seconds(0);
+
+        let funds_sent_to_contract = coins(1_000_000, NATIVE_DENOM);
+
+        let withdraw_address = String::from("gordon-gekko-address");
+
+        let msg = InstantiateMsg {
+            withdraw_address: withdraw_address.clone(),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: Some(DAY * 7),
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &funds_sent_to_contract);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // mock funds being added to contract
+        let contract_addr = env.clone().contract.address;
+        deps.querier
+            .update_balance(&contract_addr, funds_sent_to_contract);
+
+        let info = mock_info(&withdraw_address, &[]);
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // exactly at the ready time is not yet after it
+        env.block.time = Timestamp::from_seconds(28 * 86400);
+
+        let is_ready: WithdrawalReadyResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::IsWithdrawalReady { beneficiary: None },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(!is_ready.is_withdrawal_ready);
+
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::WithdrawalNotReady {});
+
+        // a second later it is
+        env.block.time = env.block.time.plus_seconds(1);
+
+        let is_ready: WithdrawalReadyResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::IsWithdrawalReady { beneficiary: None },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(is_ready.is_withdrawal_ready);
+
+        // and it stays claimable up to the end of the claim window itself
+        env.block.time = Timestamp::from_seconds(35 * 86400);
+
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // but not a second past it
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        env.block.time = env.block.time.plus_seconds(35 * 86400 + 1);
+
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+        assert_eq!(err, ContractError::WithdrawalExpired {});
+    }
+
+    #[test]
+    fn claim_withdrawal_after_window_fails() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        env.block.time = Timestamp::from_seconds(0);
+
+        let funds_sent_to_contract = coins(1_000_000, NATIVE_DENOM);
+
+        let withdraw_address = String::from("gordon-gekko-address");
+
+        // 28 days to wait, then 7 days to claim
+        let msg = InstantiateMsg {
+            withdraw_address: withdraw_address.clone(),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: Some(DAY * 7),
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &funds_sent_to_contract);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // mock funds being added to contract
+        let contract_addr = env.clone().contract.address;
+        deps.querier
+            .update_balance(&contract_addr, funds_sent_to_contract);
+
+        let info = mock_info(&withdraw_address, &[]);
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // roll time forward to 1 hr after the window closes
+        env.block.time = Timestamp::from_seconds(35 * 86400).plus_seconds(3600);
+
+        // is the withdrawal ready?
+        let is_ready: WithdrawalReadyResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::IsWithdrawalReady { beneficiary: None },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            WithdrawalReadyResponse {
+                is_withdrawal_ready: false,
+            },
+            is_ready
+        );
+
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::WithdrawalExpired {});
+
+        // starting again re-arms the timer
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // and claiming inside the window works
+        env.block.time = env.block.time.plus_seconds(30 * 86400);
+
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        execute(deps.as_mut(), env, info, msg).unwrap();
+    }
+
+    #[test]
+    fn sudo_update_config() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        env.block.time = Timestamp::from_seconds(0);
+
+        let withdraw_address = String::from("gordon-gekko-address");
+
+        let msg = InstantiateMsg {
+            withdraw_address: withdraw_address.clone(),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &[]);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // the old beneficiary has a withdrawal in flight
+        let info = mock_info(&withdraw_address, &[]);
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // governance hands the funds to someone else, with a longer delay
+        let msg = SudoMsg::UpdateConfig {
+            withdraw_address: Some(String::from("carl-fox-address")),
+            withdraw_delay: Some(DAY * 56),
+            native_denom: None,
+            rewards_destination: None,
+        };
+        let res = sudo(deps.as_mut(), env.clone(), msg).unwrap();
+
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("message_type", "sudo")
+                .add_attribute("action", "update_config")
+                .add_attribute("old_withdraw_address", "gordon-gekko-address")
+                .add_attribute("new_withdraw_address", "carl-fox-address")
+                .add_attribute("old_withdraw_delay", "time: 2419200")
+                .add_attribute("new_withdraw_delay", "time: 4838400")
+        );
+
+        // query state
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::GetConfig {}).unwrap();
+        let contract_config: Config = from_json(&res).unwrap();
+        assert_eq!(
+            Config {
+                withdraw_address: Addr::unchecked("carl-fox-address"),
+                withdraw_delay: DAY * 56,
+                withdraw_claim_window: None,
+                native_denom: NATIVE_DENOM.to_string(),
+                vesting: None,
+                rewards_destination: RewardsDestination::Contract,
+                guardians: None,
+                emergency_council: None,
+            },
+            contract_config
+        );
+
+        // the in-flight withdrawal has been cleared
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::GetWithdrawalReadyTime {},
+        )
+        .unwrap_err();
+
+        // and the old beneficiary has no say any more
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
+        let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+
+    #[test]
+    fn sudo_update_native_denom() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let msg = InstantiateMsg {
+            withdraw_address: String::from("gordon-gekko-address"),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &[]);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // an empty denom is refused
+        let msg = SudoMsg::UpdateConfig {
+            withdraw_address: None,
+            withdraw_delay: None,
+            native_denom: Some(String::new()),
+            rewards_destination: None,
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidNativeDenom {});
+
+        // otherwise the denom can change
+        let msg = SudoMsg::UpdateConfig {
+            withdraw_address: None,
+            withdraw_delay: None,
+            native_denom: Some(String::from("uosmo")),
+            rewards_destination: None,
+        };
+        let res = sudo(deps.as_mut(), env, msg).unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("message_type", "sudo")
+                .add_attribute("action", "update_config")
+                .add_attribute("old_native_denom", NATIVE_DENOM)
+                .add_attribute("new_native_denom", "uosmo")
+        );
+
+        // but not under vesting, which counts its claims in the denom
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let msg = InstantiateMsg {
+            withdraw_address: String::from("gordon-gekko-address"),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: Some(VestingSchedule {
+                start_time: Timestamp::from_seconds(0),
+                cliff_time: Timestamp::from_seconds(0),
+                end_time: Timestamp::from_seconds(100 * 86400),
+            }),
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &[]);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        let msg = SudoMsg::UpdateConfig {
+            withdraw_address: None,
+            withdraw_delay: None,
+            native_denom: Some(String::from("uosmo")),
+            rewards_destination: None,
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::NativeDenomLocked {});
+
+        // restating the current denom is harmless
+        let msg = SudoMsg::UpdateConfig {
+            withdraw_address: None,
+            withdraw_delay: None,
+            native_denom: Some(NATIVE_DENOM.to_string()),
+            rewards_destination: None,
+        };
+        sudo(deps.as_mut(), env, msg).unwrap();
+    }
+
+    #[test]
+    fn withdraw_address_handover() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        env.block.time = Timestamp::from_seconds(0);
+
+        let withdraw_address = String::from("gordon-gekko-address");
+        let multisig_address = String::from("blue-star-multisig");
+
+        let msg = InstantiateMsg {
+            withdraw_address: withdraw_address.clone(),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &[]);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        let info = mock_info(&withdraw_address, &[]);
+
+        // a withdrawal is in flight
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // random address can't nominate
+        let random = mock_info("some-random-guy", &[]);
+        let msg = ExecuteMsg::ProposeNewWithdrawAddress {
+            address: String::from("some-random-guy"),
+        };
+        let err = execute(deps.as_mut(), env.clone(), random.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let msg = ExecuteMsg::ProposeNewWithdrawAddress {
+            address: multisig_address.clone(),
+        };
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let pending: PendingWithdrawAddressResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::GetPendingWithdrawAddress {},
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            PendingWithdrawAddressResponse {
+                pending_withdraw_address: Some(Addr::unchecked(&multisig_address)),
+            },
+            pending
+        );
+
+        // only the nominee can accept
+        let msg = ExecuteMsg::AcceptWithdrawAddress {};
+        let err = execute(deps.as_mut(), env.clone(), random, msg).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let multisig = mock_info(&multisig_address, &[]);
+        let msg = ExecuteMsg::AcceptWithdrawAddress {};
+        let res = execute(deps.as_mut(), env.clone(), multisig, msg).unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "accept_withdraw_address")
+                .add_attribute("old_withdraw_address", withdraw_address)
+                .add_attribute("new_withdraw_address", multisig_address.clone())
+        );
+
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::GetConfig {}).unwrap();
+        let contract_config: Config = from_json(&res).unwrap();
+        assert_eq!(
+            contract_config.withdraw_address,
+            Addr::unchecked(multisig_address)
+        );
+
+        // the nomination is used up
+        let pending: PendingWithdrawAddressResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::GetPendingWithdrawAddress {},
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            PendingWithdrawAddressResponse {
+                pending_withdraw_address: None,
+            },
+            pending
+        );
+
+        // and the in-flight withdrawal is gone
+        query(deps.as_ref(), env, QueryMsg::GetWithdrawalReadyTime {}).unwrap_err();
+    }
+
+    fn mock_validator(address: &str) -> Validator {
+        Validator {
+            address: address.to_string(),
+            commission: Decimal::percent(5),
+            max_commission: Decimal::percent(10),
+            max_change_rate: Decimal::percent(1),
+        }
+    }
+
+    #[test]
+    fn sudo_delegate() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        env.block.time = Timestamp::from_seconds(0);
+
+        let funds_sent_to_contract = coins(3_000_000, NATIVE_DENOM);
+
+        let msg = InstantiateMsg {
+            withdraw_address: String::from("gordon-gekko-address"),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &funds_sent_to_contract);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // mock funds being added to contract
+        let contract_addr = env.clone().contract.address;
+        deps.querier
+            .update_balance(&contract_addr, funds_sent_to_contract);
+        deps.querier
+            .update_staking(NATIVE_DENOM, &[mock_validator("bluestar-valoper")], &[]);
+
+        let msg = SudoMsg::Delegate {
+            validator: String::from("bluestar-valoper"),
+            amount: Uint128::new(2_000_000),
+        };
+        let res = sudo(deps.as_mut(), env.clone(), msg).unwrap();
+
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("message_type", "sudo")
+                .add_attribute("action", "delegate")
+                .add_attribute("validator", "bluestar-valoper")
+                .add_attribute("amount", "2000000")
+                .add_message(StakingMsg::Delegate {
+                    validator: String::from("bluestar-valoper"),
+                    amount: coin(2_000_000, NATIVE_DENOM),
+                })
+        );
+
+        // can't delegate nothing
+        let msg = SudoMsg::Delegate {
+            validator: String::from("bluestar-valoper"),
+            amount: Uint128::zero(),
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidAmount {});
+
+        // or more than the contract holds
+        let msg = SudoMsg::Delegate {
+            validator: String::from("bluestar-valoper"),
+            amount: Uint128::new(5_000_000),
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientContractBalance {});
+
+        // nor to a validator that doesn't exist
+        let msg = SudoMsg::Delegate {
+            validator: String::from("anacott-valoper"),
+            amount: Uint128::new(1_000_000),
+        };
+        let err = sudo(deps.as_mut(), env, msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::ValidatorNotFound {
+                validator: String::from("anacott-valoper")
+            }
+        );
+    }
+
+    #[test]
+    fn sudo_undelegate_and_redelegate() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        env.block.time = Timestamp::from_seconds(0);
+
+        let msg = InstantiateMsg {
+            withdraw_address: String::from("gordon-gekko-address"),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &[]);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // the contract has 2 juno staked, of which 1 can be redelegated
+        let contract_addr = env.clone().contract.address;
+        let delegation = FullDelegation {
+            delegator: contract_addr,
+            validator: String::from("bluestar-valoper"),
+            amount: coin(2_000_000, NATIVE_DENOM),
+            can_redelegate: coin(1_000_000, NATIVE_DENOM),
+            accumulated_rewards: vec![],
+        };
+        deps.querier.update_staking(
+            NATIVE_DENOM,
+            &[
+                mock_validator("bluestar-valoper"),
+                mock_validator("anacott-valoper"),
+            ],
+            &[delegation],
+        );
+
+        let msg = SudoMsg::Undelegate {
+            validator: String::from("bluestar-valoper"),
+            amount: Uint128::new(2_000_000),
+        };
+        let res = sudo(deps.as_mut(), env.clone(), msg).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Staking(StakingMsg::Undelegate {
+                validator: String::from("bluestar-valoper"),
+                amount: coin(2_000_000, NATIVE_DENOM),
+            })
+        );
+
+        // can't undelegate more than is staked
+        let msg = SudoMsg::Undelegate {
+            validator: String::from("bluestar-valoper"),
+            amount: Uint128::new(3_000_000),
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientDelegation {});
+
+        // nor from a validator with no delegation
+        let msg = SudoMsg::Undelegate {
+            validator: String::from("anacott-valoper"),
+            amount: Uint128::new(1),
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientDelegation {});
+
+        // nor undelegate or redelegate nothing
+        let msg = SudoMsg::Undelegate {
+            validator: String::from("bluestar-valoper"),
+            amount: Uint128::zero(),
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidAmount {});
+
+        let msg = SudoMsg::Redelegate {
+            src_validator: String::from("bluestar-valoper"),
+            dst_validator: String::from("anacott-valoper"),
+            amount: Uint128::zero(),
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidAmount {});
+
+        let msg = SudoMsg::Redelegate {
+            src_validator: String::from("bluestar-valoper"),
+            dst_validator: String::from("anacott-valoper"),
+            amount: Uint128::new(1_000_000),
+        };
+        let res = sudo(deps.as_mut(), env.clone(), msg).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Staking(StakingMsg::Redelegate {
+                src_validator: String::from("bluestar-valoper"),
+                dst_validator: String::from("anacott-valoper"),
+                amount: coin(1_000_000, NATIVE_DENOM),
+            })
+        );
+
+        // only what can_redelegate allows can be moved
+        let msg = SudoMsg::Redelegate {
+            src_validator: String::from("bluestar-valoper"),
+            dst_validator: String::from("anacott-valoper"),
+            amount: Uint128::new(2_000_000),
+        };
+        let err = sudo(deps.as_mut(), env, msg).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientDelegation {});
+    }
+
+    #[test]
+    fn claim_rewards() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        env.block.time = Timestamp::from_seconds(0);
+
+        let msg = InstantiateMsg {
+            withdraw_address: String::from("gordon-gekko-address"),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &[]);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // nothing delegated, nothing to claim
+        let anyone = mock_info("some-random-guy", &[]);
+        let msg = ExecuteMsg::ClaimRewards {};
+        let err = execute(deps.as_mut(), env.clone(), anyone.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::NoDelegations {});
+
+        // the contract is staked with two validators
+        let contract_addr = env.clone().contract.address;
+        let delegations = [
+            FullDelegation {
+                delegator: contract_addr.clone(),
+                validator: String::from("bluestar-valoper"),
+                amount: coin(2_000_000, NATIVE_DENOM),
+                can_redelegate: coin(2_000_000, NATIVE_DENOM),
+                accumulated_rewards: coins(1_000, NATIVE_DENOM),
+            },
+            FullDelegation {
+                delegator: contract_addr,
+                validator: String::from("anacott-valoper"),
+                amount: coin(1_000_000, NATIVE_DENOM),
+                can_redelegate: coin(1_000_000, NATIVE_DENOM),
+                accumulated_rewards: coins(500, NATIVE_DENOM),
+            },
+        ];
+        deps.querier.update_staking(
+            NATIVE_DENOM,
+            &[
+                mock_validator("bluestar-valoper"),
+                mock_validator("anacott-valoper"),
+            ],
+            &delegations,
+        );
+
+        let pending: PendingRewardsResponse =
+            from_json(query(deps.as_ref(), env.clone(), QueryMsg::GetPendingRewards {}).unwrap())
+                .unwrap();
+        assert_eq!(
+            PendingRewardsResponse {
+                pending_rewards: coins(1_500, NATIVE_DENOM),
+            },
+            pending
+        );
+
+        // by default, rewards stay in the contract
+        let msg = ExecuteMsg::ClaimRewards {};
+        let res = execute(deps.as_mut(), env.clone(), anyone.clone(), msg).unwrap();
+
+        let msgs: Vec<CosmosMsg> = vec![
+            DistributionMsg::WithdrawDelegatorReward {
+                validator: String::from("bluestar-valoper"),
+            }
+            .into(),
+            DistributionMsg::WithdrawDelegatorReward {
+                validator: String::from("anacott-valoper"),
+            }
+            .into(),
+        ];
+
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "claim_rewards")
+                .add_attribute("rewards_destination", "contract")
+                .add_attribute("rewards", "1500ujuno")
+                .add_messages(msgs.clone())
+        );
+
+        // governance decides rewards belong to the community pool
+        let msg = SudoMsg::UpdateConfig {
+            withdraw_address: None,
+            withdraw_delay: None,
+            native_denom: None,
+            rewards_destination: Some(RewardsDestination::CommunityPool),
+        };
+        sudo(deps.as_mut(), env.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::ClaimRewards {};
+        let res = execute(deps.as_mut(), env, anyone, msg).unwrap();
+
+        let mut msgs = msgs;
+        msgs.push(
+            DistributionMsg::FundCommunityPool {
+                amount: coins(1_500, NATIVE_DENOM),
+            }
+            .into(),
+        );
+
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "claim_rewards")
+                .add_attribute("rewards_destination", "community_pool")
+                .add_attribute("rewards", "1500ujuno")
+                .add_messages(msgs)
+        );
+    }
+
+    #[test]
+    fn sudo_fund_community_pool() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let funds_sent_to_contract = coins(3_000_000, NATIVE_DENOM);
+
+        let msg = InstantiateMsg {
+            withdraw_address: String::from("gordon-gekko-address"),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &funds_sent_to_contract);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // mock funds being added to contract
+        let contract_addr = env.clone().contract.address;
+        deps.querier.update_balance(
+            &contract_addr,
+            vec![coin(3_000_000, NATIVE_DENOM), coin(500, "uatom")],
+        );
+
+        // with no amount, everything goes
+        let msg = SudoMsg::FundCommunityPool { amount: None };
+        let res = sudo(deps.as_mut(), env.clone(), msg).unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("message_type", "sudo")
+                .add_attribute("action", "fund_community_pool")
+                .add_attribute("amount", "3000000ujuno,500uatom")
+                .add_message(DistributionMsg::FundCommunityPool {
+                    amount: vec![coin(3_000_000, NATIVE_DENOM), coin(500, "uatom")],
+                })
+        );
+
+        // or just what was asked for
+        let msg = SudoMsg::FundCommunityPool {
+            amount: Some(coins(1_000_000, NATIVE_DENOM)),
+        };
+        let res = sudo(deps.as_mut(), env.clone(), msg).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Distribution(DistributionMsg::FundCommunityPool {
+                amount: coins(1_000_000, NATIVE_DENOM),
+            })
+        );
+
+        // but not more than the contract holds
+        let msg = SudoMsg::FundCommunityPool {
+            amount: Some(coins(1_000, "uatom")),
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientContractBalance {});
+
+        // and not nothing
+        let msg = SudoMsg::FundCommunityPool {
+            amount: Some(vec![]),
+        };
+        let err = sudo(deps.as_mut(), env, msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidAmount {});
+    }
+
+    #[test]
+    fn sudo_split_send() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let funds_sent_to_contract = coins(3_000_000, NATIVE_DENOM);
+
+        let msg = InstantiateMsg {
+            withdraw_address: String::from("gordon-gekko-address"),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &funds_sent_to_contract);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // mock funds being added to contract
+        let contract_addr = env.clone().contract.address;
+        deps.querier
+            .update_balance(&contract_addr, funds_sent_to_contract);
+
+        let msg = SudoMsg::ExecuteSplitSend {
+            recipients: vec![
+                (String::from("carl-fox-address"), Uint128::new(2_000_000)),
+                (String::from("bluestar-airlines"), Uint128::new(500_000)),
+            ],
+        };
+        let res = sudo(deps.as_mut(), env.clone(), msg).unwrap();
+
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("message_type", "sudo")
+                .add_attribute("action", "split_send")
+                .add_attribute("recipients", "2")
+                .add_attribute("total_amount", "2500000")
+                .add_message(BankMsg::Send {
+                    to_address: String::from("carl-fox-address"),
+                    amount: coins(2_000_000, NATIVE_DENOM),
+                })
+                .add_message(BankMsg::Send {
+                    to_address: String::from("bluestar-airlines"),
+                    amount: coins(500_000, NATIVE_DENOM),
+                })
+        );
+
+        // each send fits, but together they don't
+        let msg = SudoMsg::ExecuteSplitSend {
+            recipients: vec![
+                (String::from("carl-fox-address"), Uint128::new(2_000_000)),
+                (String::from("bluestar-airlines"), Uint128::new(2_000_000)),
+            ],
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientContractBalance {});
+
+        // one bad address fails the lot
+        let msg = SudoMsg::ExecuteSplitSend {
+            recipients: vec![
+                (String::from("carl-fox-address"), Uint128::new(1_000_000)),
+                (String::from("BLUESTAR-AIRLINES"), Uint128::new(1_000_000)),
+            ],
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Std(_)));
+
+        // and there must be something to send
+        let msg = SudoMsg::ExecuteSplitSend { recipients: vec![] };
+        let err = sudo(deps.as_mut(), env, msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidAmount {});
+    }
+
+    #[test]
+    fn sudo_send_other_denom() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let msg = InstantiateMsg {
+            withdraw_address: String::from("gordon-gekko-address"),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &[]);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // the contract only holds an ibc denom
+        let contract_addr = env.clone().contract.address;
+        deps.querier
+            .update_balance(&contract_addr, coins(500, "uatom"));
+
+        let msg = SudoMsg::ExecuteSend {
+            recipient: String::from("carl-fox-address"),
+            amount: Uint128::new(200),
+            denom: Some(String::from("uatom")),
+        };
+        let res = sudo(deps.as_mut(), env.clone(), msg).unwrap();
+
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("message_type", "sudo")
+                .add_attribute("action", "send")
+                .add_message(BankMsg::Send {
+                    to_address: String::from("carl-fox-address"),
+                    amount: coins(200, "uatom"),
+                })
+        );
+
+        // asking for too much says which denom, and by how much
+        let msg = SudoMsg::ExecuteSend {
+            recipient: String::from("carl-fox-address"),
+            amount: Uint128::new(800),
+            denom: Some(String::from("uatom")),
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InsufficientDenomBalance {
+                denom: String::from("uatom"),
+                shortfall: Uint128::new(300),
+            }
+        );
+
+        // same for a denom the contract doesn't hold at all
+        let msg = SudoMsg::ExecuteSend {
+            recipient: String::from("carl-fox-address"),
+            amount: Uint128::new(10),
+            denom: Some(String::from("uosmo")),
+        };
+        let err = sudo(deps.as_mut(), env, msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InsufficientDenomBalance {
+                denom: String::from("uosmo"),
+                shortfall: Uint128::new(10),
+            }
+        );
+    }
+
+    // answers as a cw20 token at bluestar-token,
+    // holding 1000 for every address
+    fn mock_cw20_token(query: &WasmQuery) -> QuerierResult {
+        match query {
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == "bluestar-token" => {
+                let res = match from_json(msg).unwrap() {
+                    Cw20QueryMsg::TokenInfo {} => to_json_binary(&TokenInfoResponse {
+                        name: String::from("Bluestar"),
+                        symbol: String::from("BLUE"),
+                        decimals: 6,
+                        total_supply: Uint128::new(1_000_000),
+                    }),
+                    Cw20QueryMsg::Balance { .. } => to_json_binary(&BalanceResponse {
+                        balance: Uint128::new(1_000),
+                    }),
+                    _ => unimplemented!(),
+                };
+                SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+            }
+            _ => SystemResult::Err(SystemError::NoSuchContract {
+                addr: String::from("not-a-token"),
+            }),
+        }
+    }
+
+    #[test]
+    fn receive_cw20() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let msg = InstantiateMsg {
+            withdraw_address: String::from("gordon-gekko-address"),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &[]);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        deps.querier.update_wasm(mock_cw20_token);
+
+        // the token contract calls receive, on behalf of the sender
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("bud-fox-address"),
+            amount: Uint128::new(1_000),
+            msg: Binary::default(),
+        });
+        let info = mock_info("bluestar-token", &[]);
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "receive_cw20")
+                .add_attribute("token", "bluestar-token")
+                .add_attribute("sender", "bud-fox-address")
+                .add_attribute("amount", "1000")
+        );
+
+        // receiving only records the deposit
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::GetCw20Balances {}).unwrap();
+        let value: Cw20BalancesResponse = from_json(&res).unwrap();
+        assert!(value.balances.is_empty());
+
+        // only a token can be tracked
+        let msg = SudoMsg::TrackCw20 {
+            token: String::from("darien-taylor-address"),
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::NotCw20Token {});
+
+        let msg = SudoMsg::TrackCw20 {
+            token: String::from("bluestar-token"),
+        };
+        let res = sudo(deps.as_mut(), env.clone(), msg).unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("message_type", "sudo")
+                .add_attribute("action", "track_cw20")
+                .add_attribute("token", "bluestar-token")
+        );
+
+        // and only once
+        let msg = SudoMsg::TrackCw20 {
+            token: String::from("bluestar-token"),
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::Cw20AlreadyTracked {});
+
+        // the token is now tracked
+        let res = query(deps.as_ref(), env, QueryMsg::GetCw20Balances {}).unwrap();
+        let value: Cw20BalancesResponse = from_json(&res).unwrap();
+        assert_eq!(
+            value.balances,
+            vec![Cw20CoinVerified {
+                address: Addr::unchecked("bluestar-token"),
+                amount: Uint128::new(1_000),
+            }]
+        );
+    }
+
+    #[test]
+    fn receive_from_non_cw20_fails() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let funds_sent_to_contract = coins(1_000_000, NATIVE_DENOM);
+
+        let msg = InstantiateMsg {
+            withdraw_address: String::from("gordon-gekko-address"),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &funds_sent_to_contract);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // mock funds being added to contract
+        let contract_addr = env.clone().contract.address;
+        deps.querier
+            .update_balance(&contract_addr, funds_sent_to_contract);
+
+        // a wallet calls receive itself, rather than via a token
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("darien-taylor-address"),
+            amount: Uint128::new(1_000),
+            msg: Binary::default(),
+        });
+        let info = mock_info("darien-taylor-address", &[]);
+        let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        assert_eq!(err, ContractError::NotCw20Token {});
+
+        // so nothing is recorded
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::GetCw20Balances {}).unwrap();
+        let value: Cw20BalancesResponse = from_json(&res).unwrap();
+        assert!(value.balances.is_empty());
+
+        // and governance can still send everything
+        let msg = SudoMsg::ExecuteSendAll {
+            recipient: String::from("carl-fox-address"),
+        };
+        let res = sudo(deps.as_mut(), env, msg).unwrap();
+        assert_eq!(
+            res.messages.into_iter().map(|m| m.msg).collect::<Vec<_>>(),
+            vec![CosmosMsg::from(BankMsg::Send {
+                to_address: String::from("carl-fox-address"),
+                amount: coins(1_000_000, NATIVE_DENOM),
+            })]
+        );
+    }
+
+    #[test]
+    fn sudo_untrack_cw20() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let funds_sent_to_contract = coins(1_000_000, NATIVE_DENOM);
+
+        let msg = InstantiateMsg {
+            withdraw_address: String::from("gordon-gekko-address"),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &funds_sent_to_contract);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // mock funds being added to contract
+        let contract_addr = env.clone().contract.address;
+        deps.querier
+            .update_balance(&contract_addr, funds_sent_to_contract);
+
+        deps.querier.update_wasm(mock_cw20_token);
+
+        let msg = SudoMsg::TrackCw20 {
+            token: String::from("bluestar-token"),
+        };
+        sudo(deps.as_mut(), env.clone(), msg).unwrap();
+
+        // later the token stops answering
+        deps.querier.update_wasm(|_| {
+            SystemResult::Err(SystemError::NoSuchContract {
+                addr: String::from("bluestar-token"),
+            })
+        });
+
+        // which leaves it out, rather than failing the send
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::GetCw20Balances {}).unwrap();
+        let value: Cw20BalancesResponse = from_json(&res).unwrap();
+        assert!(value.balances.is_empty());
+
+        let msg = SudoMsg::ExecuteSendAll {
+            recipient: String::from("carl-fox-address"),
+        };
+        let res = sudo(deps.as_mut(), env.clone(), msg).unwrap();
+        assert_eq!(res.messages.len(), 1);
+
+        // governance can stop tracking it altogether
+        let msg = SudoMsg::UntrackCw20 {
+            token: String::from("bluestar-token"),
+        };
+        let res = sudo(deps.as_mut(), env.clone(), msg).unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("message_type", "sudo")
+                .add_attribute("action", "untrack_cw20")
+                .add_attribute("token", "bluestar-token")
+        );
+
+        // but only once
+        let msg = SudoMsg::UntrackCw20 {
+            token: String::from("bluestar-token"),
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::Cw20NotTracked {});
+
+        // the history records which token
+        let msg = QueryMsg::GetActionHistory {
+            start_after: None,
+            limit: None,
+        };
+        let res = query(deps.as_ref(), env, msg).unwrap();
+        let value: ActionHistoryResponse = from_json(&res).unwrap();
+        let action = value.actions.last().unwrap().action.clone();
+        assert_eq!(
+            action,
+            ActionRecord {
+                cw20_amount: vec![Cw20CoinVerified {
+                    address: Addr::unchecked("bluestar-token"),
+                    amount: Uint128::zero(),
+                }],
+                ..ActionRecord::new(ActionKind::UntrackCw20, action.time)
+            }
+        );
+    }
+
+    #[test]
+    fn sudo_ibc_transfer() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let funds_sent_to_contract = coins(3_000_000, NATIVE_DENOM);
+
+        let msg = InstantiateMsg {
+            withdraw_address: String::from("gordon-gekko-address"),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &funds_sent_to_contract);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // mock funds being added to contract
+        let contract_addr = env.clone().contract.address;
+        deps.querier
+            .update_balance(&contract_addr, funds_sent_to_contract);
+
+        let msg = SudoMsg::IbcTransfer {
+            channel_id: String::from("channel-0"),
+            to_address: String::from("cosmos1carlfox"),
+            amount: coin(2_000_000, NATIVE_DENOM),
+            timeout_seconds: 600,
+        };
+        let res = sudo(deps.as_mut(), env.clone(), msg).unwrap();
+
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("message_type", "sudo")
+                .add_attribute("action", "ibc_transfer")
+                .add_attribute("channel_id", "channel-0")
+                .add_attribute("to_address", "cosmos1carlfox")
+                .add_attribute("amount", "2000000ujuno")
+                .add_message(IbcMsg::Transfer {
+                    channel_id: String::from("channel-0"),
+                    to_address: String::from("cosmos1carlfox"),
+                    amount: coin(2_000_000, NATIVE_DENOM),
+                    timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(600)),
+                })
+        );
+
+        // same balance checks as a send
+        let msg = SudoMsg::IbcTransfer {
+            channel_id: String::from("channel-0"),
+            to_address: String::from("cosmos1carlfox"),
+            amount: coin(4_000_000, NATIVE_DENOM),
+            timeout_seconds: 600,
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InsufficientDenomBalance {
+                denom: NATIVE_DENOM.to_string(),
+                shortfall: Uint128::new(1_000_000),
+            }
+        );
+
+        // and the transfer must be able to be relayed
+        let msg = SudoMsg::IbcTransfer {
+            channel_id: String::from("channel-0"),
+            to_address: String::from("cosmos1carlfox"),
+            amount: coin(1_000_000, NATIVE_DENOM),
+            timeout_seconds: 0,
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidIbcTimeout {});
+
+        // nor so far off that it overflows
+        let msg = SudoMsg::IbcTransfer {
+            channel_id: String::from("channel-0"),
+            to_address: String::from("cosmos1carlfox"),
+            amount: coin(1_000_000, NATIVE_DENOM),
+            timeout_seconds: u64::MAX,
+        };
+        let err = sudo(deps.as_mut(), env, msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidIbcTimeout {});
+    }
+
+    #[test]
+    fn action_history() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        env.block.time = Timestamp::from_seconds(0);
+
+        let funds_sent_to_contract = coins(3_000_000, NATIVE_DENOM);
+
+        let msg = InstantiateMsg {
+            withdraw_address: String::from("gordon-gekko-address"),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &funds_sent_to_contract);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // mock funds being added to contract
+        let contract_addr = env.clone().contract.address;
+        deps.querier
+            .update_balance(&contract_addr, funds_sent_to_contract);
+
+        // nothing has happened yet
+        let msg = QueryMsg::GetActionHistory {
+            start_after: None,
+            limit: None,
+        };
+        let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+        let value: ActionHistoryResponse = from_json(&res).unwrap();
+        assert_eq!(value.actions, vec![]);
+
+        // the withdraw address starts then cancels a withdrawal
+        let info = mock_info("gordon-gekko-address", &[]);
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        let msg = ExecuteMsg::CancelWithdraw {};
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // then governance sends some funds elsewhere
+        env.block.time = Timestamp::from_seconds(100);
+        let msg = SudoMsg::ExecuteSend {
+            recipient: String::from("carl-fox-address"),
+            amount: Uint128::new(1_000_000),
+            denom: None,
+        };
+        sudo(deps.as_mut(), env.clone(), msg).unwrap();
+
+        let msg = QueryMsg::GetActionHistory {
+            start_after: None,
+            limit: Some(2),
+        };
+        let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+        let value: ActionHistoryResponse = from_json(&res).unwrap();
+        assert_eq!(
+            value.actions,
+            vec![
+                ActionHistoryEntry {
+                    id: 0,
+                    action: ActionRecord {
+                        actor: Some(Addr::unchecked("gordon-gekko-address")),
+                        ..ActionRecord::new(ActionKind::StartWithdraw, Timestamp::from_seconds(0))
+                    },
+                },
+                ActionHistoryEntry {
+                    id: 1,
+                    action: ActionRecord {
+                        actor: Some(Addr::unchecked("gordon-gekko-address")),
+                        ..ActionRecord::new(ActionKind::CancelWithdraw, Timestamp::from_seconds(0))
+                    },
+                },
+            ]
+        );
+
+        // and the next page
+        let msg = QueryMsg::GetActionHistory {
+            start_after: Some(1),
+            limit: Some(2),
+        };
+        let res = query(deps.as_ref(), env, msg).unwrap();
+        let value: ActionHistoryResponse = from_json(&res).unwrap();
+        assert_eq!(
+            value.actions,
+            vec![ActionHistoryEntry {
+                id: 2,
+                action: ActionRecord {
+                    amount: coins(1_000_000, NATIVE_DENOM),
+                    recipient: Some(String::from("carl-fox-address")),
+                    ..ActionRecord::new(ActionKind::Send, Timestamp::from_seconds(100))
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn totals_accumulate_per_denom() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let msg = InstantiateMsg {
+            withdraw_address: String::from("gordon-gekko-address"),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &[]);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // mock native and ibc funds being added to contract
+        let contract_addr = env.clone().contract.address;
+        deps.querier.update_balance(
+            &contract_addr,
+            vec![coin(3_000_000, NATIVE_DENOM), coin(500, "uatom")],
+        );
+
+        // nothing has left the contract yet
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::GetTotals {}).unwrap();
+        let value: Totals = from_json(&res).unwrap();
+        assert_eq!(value, Totals::default());
+
+        // two sends of the native denom, one of the ibc denom
+        for (amount, denom) in [(1_000_000, None), (500_000, None), (200, Some("uatom"))] {
+            let msg = SudoMsg::ExecuteSend {
+                recipient: String::from("carl-fox-address"),
+                amount: Uint128::new(amount),
+                denom: denom.map(String::from),
+            };
+            sudo(deps.as_mut(), env.clone(), msg).unwrap();
+        }
+
+        let res = query(deps.as_ref(), env, QueryMsg::GetTotals {}).unwrap();
+        let value: Totals = from_json(&res).unwrap();
+        assert_eq!(
+            value,
+            Totals {
+                sent: vec![coin(200, "uatom"), coin(1_500_000, NATIVE_DENOM)],
+                ..Totals::default()
+            }
+        );
+    }
+
+    #[test]
+    fn query_balance() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let msg = InstantiateMsg {
+            withdraw_address: String::from("gordon-gekko-address"),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &[]);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // an empty contract still reports its native denom
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::GetBalance {}).unwrap();
+        let value: ContractBalanceResponse = from_json(&res).unwrap();
+        assert_eq!(
+            value,
+            ContractBalanceResponse {
+                native: coin(0, NATIVE_DENOM),
+                other: vec![],
+                staked: None,
+            }
+        );
+
+        // mock native and ibc funds being added to contract
+        // and some of the native funds being staked
+        let contract_addr = env.clone().contract.address;
+        deps.querier.update_balance(
+            &contract_addr,
+            vec![coin(1_000_000, NATIVE_DENOM), coin(500, "uatom")],
+        );
+        let delegations = ["bluestar-valoper", "anacott-valoper"].map(|validator| FullDelegation {
+            delegator: contract_addr.clone(),
+            validator: String::from(validator),
+            amount: coin(1_000_000, NATIVE_DENOM),
+            can_redelegate: coin(1_000_000, NATIVE_DENOM),
+            accumulated_rewards: vec![],
+        });
+        deps.querier.update_staking(
+            NATIVE_DENOM,
+            &[
+                mock_validator("bluestar-valoper"),
+                mock_validator("anacott-valoper"),
+            ],
+            &delegations,
+        );
+
+        let res = query(deps.as_ref(), env, QueryMsg::GetBalance {}).unwrap();
+        let value: ContractBalanceResponse = from_json(&res).unwrap();
+        assert_eq!(
+            value,
+            ContractBalanceResponse {
+                native: coin(1_000_000, NATIVE_DENOM),
+                other: vec![coin(500, "uatom")],
+                staked: Some(coin(2_000_000, NATIVE_DENOM)),
+            }
+        );
+    }
+
+    #[test]
+    fn query_withdrawal_status() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        env.block.time = Timestamp::from_seconds(0);
+
+        let funds_sent_to_contract = coins(1_000_000, NATIVE_DENOM);
+
+        let withdraw_address = String::from("gordon-gekko-address");
+
+        // 28 days to wait, then 7 days to claim
+        let msg = InstantiateMsg {
+            withdraw_address: withdraw_address.clone(),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: Some(DAY * 7),
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &funds_sent_to_contract);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // mock funds being added to contract
+        let contract_addr = env.clone().contract.address;
+        deps.querier
+            .update_balance(&contract_addr, funds_sent_to_contract.clone());
+
+        let status = |deps: &cosmwasm_std::OwnedDeps<_, _, _>, env| -> WithdrawalStatusResponse {
+            from_json(
+                query(
+                    deps.as_ref(),
+                    env,
+                    QueryMsg::GetWithdrawalStatus { beneficiary: None },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+
+        // nothing requested yet
+        assert_eq!(
+            status(&deps, env.clone()),
+            WithdrawalStatusResponse {
+                status: WithdrawalStatus::None,
+                amount: vec![],
+                cw20_amount: vec![],
+            }
+        );
+
+        let info = mock_info(&withdraw_address, &[]);
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // one day in
+        env.block.time = Timestamp::from_seconds(86400);
+        assert_eq!(
+            status(&deps, env.clone()),
+            WithdrawalStatusResponse {
+                status: WithdrawalStatus::Pending {
+                    ready_at: Expiration::AtTime(Timestamp::from_seconds(28 * 86400)),
+                    remaining: DAY * 27,
+                },
+                amount: funds_sent_to_contract.clone(),
+                cw20_amount: vec![],
+            }
+        );
+
+        // 1 hr after the delay
+        env.block.time = Timestamp::from_seconds(28 * 86400).plus_seconds(3600);
+        assert_eq!(
+            status(&deps, env.clone()),
+            WithdrawalStatusResponse {
+                status: WithdrawalStatus::Ready {
+                    since: Expiration::AtTime(Timestamp::from_seconds(28 * 86400)),
+                    expires_at: Some(Expiration::AtTime(Timestamp::from_seconds(35 * 86400))),
+                },
+                amount: funds_sent_to_contract,
+                cw20_amount: vec![],
+            }
+        );
+
+        // 1 hr after the window closes
+        env.block.time = Timestamp::from_seconds(35 * 86400).plus_seconds(3600);
+        assert_eq!(
+            status(&deps, env.clone()),
+            WithdrawalStatusResponse {
+                status: WithdrawalStatus::Expired {
+                    expired_at: Expiration::AtTime(Timestamp::from_seconds(35 * 86400)),
+                },
+                amount: vec![],
+                cw20_amount: vec![],
+            }
+        );
+
+        // a partial request, then vetoed by governance
+        let msg = ExecuteMsg::StartWithdraw {
+            amount: Some(coins(1_000, NATIVE_DENOM)),
+        };
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        assert_eq!(
+            status(&deps, env.clone()).amount,
+            coins(1_000, NATIVE_DENOM)
+        );
+
+        let msg = SudoMsg::VetoWithdrawal {
+            reason: String::from("greed is not good"),
+            beneficiary: None,
+        };
+        sudo(deps.as_mut(), env.clone(), msg).unwrap();
+        assert_eq!(
+            status(&deps, env),
+            WithdrawalStatusResponse {
+                status: WithdrawalStatus::Vetoed {
+                    reason: String::from("greed is not good"),
+                },
+                amount: vec![],
+                cw20_amount: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn simulate_withdraw_and_sudo() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        env.block.time = Timestamp::from_seconds(0);
+
+        let funds_sent_to_contract = coins(1_000_000, NATIVE_DENOM);
+
+        let withdraw_address = String::from("gordon-gekko-address");
+
+        let msg = InstantiateMsg {
+            withdraw_address: withdraw_address.clone(),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &funds_sent_to_contract);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // mock funds being added to contract
+        let contract_addr = env.clone().contract.address;
+        deps.querier
+            .update_balance(&contract_addr, funds_sent_to_contract.clone());
+
+        // nothing has been started
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::SimulateWithdraw { beneficiary: None },
+        )
+        .unwrap();
+        let value: SimulationResponse = from_json(&res).unwrap();
+        assert_eq!(value.messages, vec![]);
+        assert!(value.error.is_some());
+
+        let info = mock_info(&withdraw_address, &[]);
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // roll time forward in env to 1 hr after the 28 day delay
+        env.block.time = Timestamp::from_seconds(28 * 86400).plus_seconds(3600);
+
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::SimulateWithdraw { beneficiary: None },
+        )
+        .unwrap();
+        let value: SimulationResponse = from_json(&res).unwrap();
+        assert_eq!(
+            value,
+            SimulationResponse {
+                messages: vec![CosmosMsg::Bank(BankMsg::Send {
+                    to_address: withdraw_address,
+                    amount: funds_sent_to_contract.clone(),
+                })],
+                error: None,
+            }
+        );
+
+        // the withdrawal is still pending afterwards
+        let is_ready: WithdrawalReadyResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::IsWithdrawalReady { beneficiary: None },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(is_ready.is_withdrawal_ready);
+
+        // a proposal that would fail
+        let msg = QueryMsg::SimulateSudo {
+            msg: SudoMsg::ExecuteSend {
+                recipient: String::from("carl-fox-address"),
+                amount: Uint128::new(2_000_000),
+                denom: None,
+            },
+        };
+        let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+        let value: SimulationResponse = from_json(&res).unwrap();
+        assert_eq!(value.messages, vec![]);
+        assert!(value.error.is_some());
+
+        // and one that would succeed
+        let msg = QueryMsg::SimulateSudo {
+            msg: SudoMsg::ExecuteSend {
+                recipient: String::from("carl-fox-address"),
+                amount: Uint128::new(500_000),
+                denom: None,
+            },
+        };
+        let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+        let value: SimulationResponse = from_json(&res).unwrap();
+        assert_eq!(
+            value,
+            SimulationResponse {
+                messages: vec![CosmosMsg::Bank(BankMsg::Send {
+                    to_address: String::from("carl-fox-address"),
+                    amount: coins(500_000, NATIVE_DENOM),
+                })],
+                error: None,
+            }
+        );
+
+        // nothing was recorded
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::GetTotals {}).unwrap();
+        let value: Totals = from_json(&res).unwrap();
+        assert_eq!(value, Totals::default());
+
+        let msg = QueryMsg::GetActionHistory {
+            start_after: None,
+            limit: None,
+        };
+        let res = query(deps.as_ref(), env, msg).unwrap();
+        let value: ActionHistoryResponse = from_json(&res).unwrap();
+        assert_eq!(value.actions.len(), 1);
+    }
+
+    #[test]
+    fn invalid_beneficiaries() {
+        let mut deps = mock_dependencies();
+
+        let share = |address: &str, shares: u64| BeneficiaryShare {
+            address: String::from(address),
+            shares,
+        };
+        let now = mock_env().block.time;
+        let vesting = VestingSchedule {
+            start_time: now,
+            cliff_time: now,
+            end_time: now.plus_seconds(86400),
+        };
+
+        // empty, duplicated, zero shares, or alongside vesting
+        let cases = vec![
+            (vec![], None),
+            (
+                vec![share("bud-fox-address", 1), share("bud-fox-address", 1)],
+                None,
+            ),
+            (vec![share("bud-fox-address", 0)], None),
+            (vec![share("bud-fox-address", 1)], Some(vesting)),
+        ];
+
+        for (beneficiaries, vesting) in cases {
+            let msg = InstantiateMsg {
+                withdraw_address: String::from("gordon-gekko-address"),
+                withdraw_delay: DAY * 28,
+                withdraw_claim_window: None,
+                native_denom: NATIVE_DENOM.to_string(),
+                vesting,
+                rewards_destination: None,
+                emergency_council: None,
+                beneficiaries: Some(beneficiaries),
+            };
+
+            let instantiate_info = mock_info("bud-fox-address", &[]);
+            let err = instantiate(deps.as_mut(), mock_env(), instantiate_info, msg).unwrap_err();
+            assert_eq!(err, ContractError::InvalidBeneficiaries {});
+        }
+    }
+
+    #[test]
+    fn beneficiary_withdrawals() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        env.block.time = Timestamp::from_seconds(0);
+
+        let funds_sent_to_contract = coins(1_000_000, NATIVE_DENOM);
+
+        // bud fox holds one share, carl fox three
+        let msg = InstantiateMsg {
+            withdraw_address: String::from("gordon-gekko-address"),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: Some(vec![
+                BeneficiaryShare {
+                    address: String::from("bud-fox-address"),
+                    shares: 1,
+                },
+                BeneficiaryShare {
+                    address: String::from("carl-fox-address"),
+                    shares: 3,
+                },
+            ]),
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &funds_sent_to_contract);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // mock funds being added to contract
+        let contract_addr = env.clone().contract.address;
+        deps.querier
+            .update_balance(&contract_addr, funds_sent_to_contract);
+
+        // guardians cannot gate beneficiary withdrawals
+        let msg = SudoMsg::SetGuardians {
+            guardians: vec![String::from("lou-mannheim-address")],
+            threshold: 1,
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::GuardiansUnsupported {});
+
+        // but removing them is always allowed
+        let msg = SudoMsg::SetGuardians {
+            guardians: vec![],
+            threshold: 0,
+        };
+        sudo(deps.as_mut(), env.clone(), msg).unwrap();
+
+        // only beneficiaries can withdraw
+        let info = mock_info("gordon-gekko-address", &[]);
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
+        let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // and always for the whole share
+        let bud_fox = mock_info("bud-fox-address", &[]);
+        let msg = ExecuteMsg::StartWithdraw {
+            amount: Some(coins(1_000, NATIVE_DENOM)),
+        };
+        let err = execute(deps.as_mut(), env.clone(), bud_fox.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::BeneficiaryPartialWithdrawal {});
+
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
+        execute(deps.as_mut(), env.clone(), bud_fox.clone(), msg).unwrap();
+
+        // carl fox has not started one
+        let carl_fox = mock_info("carl-fox-address", &[]);
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        let err = execute(deps.as_mut(), env.clone(), carl_fox, msg).unwrap_err();
+        assert_eq!(err, ContractError::NoPendingWithdrawal {});
+
+        // bud fox has to wait out the delay
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        let err = execute(deps.as_mut(), env.clone(), bud_fox.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::WithdrawalNotReady {});
+
+        // roll time forward in env to 1 hr after the 28 day delay
+        env.block.time = Timestamp::from_seconds(28 * 86400).plus_seconds(3600);
+
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        let res = execute(deps.as_mut(), env.clone(), bud_fox.clone(), msg).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("bud-fox-address"),
+                amount: coins(250_000, NATIVE_DENOM),
+            })
+        );
+
+        // the bank send leaves the rest
+        deps.querier
+            .update_balance(&contract_addr, coins(750_000, NATIVE_DENOM));
+
+        // a claim uses up the request
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        let err = execute(deps.as_mut(), env.clone(), bud_fox, msg).unwrap_err();
+        assert_eq!(err, ContractError::NoPendingWithdrawal {});
+
+        let res = query(deps.as_ref(), env, QueryMsg::GetBeneficiaries {}).unwrap();
+        let value: BeneficiariesResponse = from_json(&res).unwrap();
+        assert_eq!(
+            value.beneficiaries,
+            vec![
+                BeneficiaryInfo {
+                    address: Addr::unchecked("bud-fox-address"),
+                    shares: 1,
+                    claimed: Uint128::new(250_000),
+                    withdrawal_ready: None,
+                    claimable: Uint128::zero(),
+                },
+                BeneficiaryInfo {
+                    address: Addr::unchecked("carl-fox-address"),
+                    shares: 3,
+                    claimed: Uint128::zero(),
+                    withdrawal_ready: None,
+                    claimable: Uint128::new(750_000),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn sudo_beneficiary_veto_and_cancel() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        env.block.time = Timestamp::from_seconds(0);
+
+        let funds_sent_to_contract = coins(1_000_000, NATIVE_DENOM);
+
+        let msg = InstantiateMsg {
+            withdraw_address: String::from("gordon-gekko-address"),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: Some(vec![
+                BeneficiaryShare {
+                    address: String::from("bud-fox-address"),
+                    shares: 1,
+                },
+                BeneficiaryShare {
+                    address: String::from("carl-fox-address"),
+                    shares: 3,
+                },
+            ]),
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &funds_sent_to_contract);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        let contract_addr = env.clone().contract.address;
+        deps.querier
+            .update_balance(&contract_addr, funds_sent_to_contract);
+
+        // nothing to veto yet
+        let msg = SudoMsg::VetoWithdrawal {
+            reason: String::from("blue horseshoe loves anacott steel"),
+            beneficiary: None,
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::NoPendingWithdrawal {});
+
+        // and only beneficiaries can be named
+        let msg = SudoMsg::CancelWithdraw {
+            beneficiary: Some(String::from("gordon-gekko-address")),
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::UnknownBeneficiary {});
+
+        // queries have to say whose withdrawal they are about
+        let err = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::GetWithdrawalStatus { beneficiary: None },
+        )
+        .unwrap_err();
+        assert!(matches!(err, StdError::GenericErr { .. }));
+
+        let bud_fox = mock_info("bud-fox-address", &[]);
+        let carl_fox = mock_info("carl-fox-address", &[]);
+        for info in [bud_fox.clone(), carl_fox.clone()] {
+            let msg = ExecuteMsg::StartWithdraw { amount: None };
+            execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        }
+
+        // roll time forward in env to 1 hr after the 28 day delay
+        env.block.time = Timestamp::from_seconds(28 * 86400).plus_seconds(3600);
+
+        let value: WithdrawalStatusResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::GetWithdrawalStatus {
+                    beneficiary: Some(String::from("carl-fox-address")),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            value.status,
+            WithdrawalStatus::Ready {
+                since: Expiration::AtTime(Timestamp::from_seconds(28 * 86400)),
+                expires_at: None,
+            }
+        );
+        assert_eq!(value.amount, coins(750_000, NATIVE_DENOM));
+
+        // the dry run claims as the beneficiary
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::SimulateWithdraw {
+                beneficiary: Some(String::from("bud-fox-address")),
+            },
+        )
+        .unwrap();
+        let value: SimulationResponse = from_json(&res).unwrap();
+        assert_eq!(
+            value,
+            SimulationResponse {
+                messages: vec![CosmosMsg::Bank(BankMsg::Send {
+                    to_address: String::from("bud-fox-address"),
+                    amount: coins(250_000, NATIVE_DENOM),
+                })],
+                error: None,
+            }
+        );
+
+        // an unnamed veto blocks every pending beneficiary withdrawal
+        let msg = SudoMsg::VetoWithdrawal {
+            reason: String::from("blue horseshoe loves anacott steel"),
+            beneficiary: None,
+        };
+        let res = sudo(deps.as_mut(), env.clone(), msg).unwrap();
+        assert_eq!(res.attributes[3], ("beneficiary", "bud-fox-address"));
+        assert_eq!(res.attributes[4], ("beneficiary", "carl-fox-address"));
+
+        for info in [bud_fox.clone(), carl_fox.clone()] {
+            let msg = ExecuteMsg::ExecuteWithdraw {};
+            let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::WithdrawalVetoed {
+                    reason: String::from("blue horseshoe loves anacott steel"),
+                }
+            );
+        }
+
+        let value: WithdrawalReadyResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::IsWithdrawalReady {
+                    beneficiary: Some(String::from("bud-fox-address")),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(!value.is_withdrawal_ready);
+
+        // a named cancel only retracts that beneficiary's withdrawal
+        let msg = SudoMsg::CancelWithdraw {
+            beneficiary: Some(String::from("carl-fox-address")),
+        };
+        let res = sudo(deps.as_mut(), env.clone(), msg).unwrap();
+        assert_eq!(res.attributes[2], ("beneficiary", "carl-fox-address"));
+
+        // the history records whose withdrawals governance blocked
+        let msg = QueryMsg::GetActionHistory {
+            start_after: Some(1),
+            limit: None,
+        };
+        let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+        let value: ActionHistoryResponse = from_json(&res).unwrap();
+        assert_eq!(
+            value
+                .actions
+                .into_iter()
+                .map(|entry| (entry.action.kind, entry.action.recipient))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    ActionKind::VetoWithdrawal,
+                    Some(String::from("bud-fox-address"))
+                ),
+                (
+                    ActionKind::VetoWithdrawal,
+                    Some(String::from("carl-fox-address"))
+                ),
+                (
+                    ActionKind::CancelWithdraw,
+                    Some(String::from("carl-fox-address"))
+                ),
+            ]
+        );
+
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        let err = execute(deps.as_mut(), env.clone(), carl_fox, msg).unwrap_err();
+        assert_eq!(err, ContractError::NoPendingWithdrawal {});
+
+        let value: WithdrawalStatusResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::GetWithdrawalStatus {
+                    beneficiary: Some(String::from("bud-fox-address")),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            value.status,
+            WithdrawalStatus::Vetoed {
+                reason: String::from("blue horseshoe loves anacott steel"),
+            }
+        );
+        assert_eq!(value.amount, vec![]);
+
+        // a fresh request clears the veto
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
+        execute(deps.as_mut(), env.clone(), bud_fox.clone(), msg).unwrap();
+
+        env.block.time = env.block.time.plus_seconds(28 * 86400 + 1);
+
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        let res = execute(deps.as_mut(), env, bud_fox, msg).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("bud-fox-address"),
+                amount: coins(250_000, NATIVE_DENOM),
+            })
+        );
+    }
+
+    #[test]
+    fn guardian_approvals() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        env.block.time = Timestamp::from_seconds(0);
+
+        let funds_sent_to_contract = coins(1_000_000, NATIVE_DENOM);
+
+        let withdraw_address = String::from("gordon-gekko-address");
+
+        let msg = InstantiateMsg {
+            withdraw_address: withdraw_address.clone(),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &funds_sent_to_contract);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // mock funds being added to contract
+        let contract_addr = env.clone().contract.address;
+        deps.querier
+            .update_balance(&contract_addr, funds_sent_to_contract);
+
+        // more approvals than guardians, or the same guardian twice
+        let msg = SudoMsg::SetGuardians {
+            guardians: vec![String::from("carl-fox-address")],
+            threshold: 2,
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidGuardians {});
+
+        let msg = SudoMsg::SetGuardians {
+            guardians: vec![
+                String::from("carl-fox-address"),
+                String::from("carl-fox-address"),
+            ],
+            threshold: 1,
+        };
+        let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidGuardians {});
+
+        // two of three
+        let msg = SudoMsg::SetGuardians {
+            guardians: vec![
+                String::from("carl-fox-address"),
+                String::from("lou-mannheim-address"),
+                String::from("darien-taylor-address"),
+            ],
+            threshold: 2,
+        };
+        let res = sudo(deps.as_mut(), env.clone(), msg).unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("message_type", "sudo")
+                .add_attribute("action", "set_guardians")
+                .add_attribute("guardians", "3")
+                .add_attribute("threshold", "2")
+        );
+
+        // nothing to approve yet
+        let carl_fox = mock_info("carl-fox-address", &[]);
+        let msg = ExecuteMsg::ApproveWithdrawal {};
+        let err = execute(deps.as_mut(), env.clone(), carl_fox.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::NoPendingWithdrawal {});
+
+        let info = mock_info(&withdraw_address, &[]);
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // the withdraw_address is not a guardian
+        let msg = ExecuteMsg::ApproveWithdrawal {};
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // roll time forward in env to 1 hr after the 28 day delay
+        env.block.time = Timestamp::from_seconds(28 * 86400).plus_seconds(3600);
+
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InsufficientApprovals {
+                approvals: 0,
+                threshold: 2
+            }
+        );
+
+        // past the delay, the status reports the approvals still needed
+        let value: WithdrawalStatusResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::GetWithdrawalStatus { beneficiary: None },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            value.status,
+            WithdrawalStatus::AwaitingApproval {
+                approvals: 0,
+                threshold: 2,
+            }
+        );
+        assert_eq!(value.amount, coins(1_000_000, NATIVE_DENOM));
+
+        let msg = ExecuteMsg::ApproveWithdrawal {};
+        execute(deps.as_mut(), env.clone(), carl_fox.clone(), msg).unwrap();
+
+        // approving twice does not count twice
+        let msg = ExecuteMsg::ApproveWithdrawal {};
+        let err = execute(deps.as_mut(), env.clone(), carl_fox, msg).unwrap_err();
+        assert_eq!(err, ContractError::AlreadyApproved {});
+
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InsufficientApprovals {
+                approvals: 1,
+                threshold: 2
+            }
+        );
+
+        let value: WithdrawalReadyResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::IsWithdrawalReady { beneficiary: None },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(!value.is_withdrawal_ready);
+
+        let lou_mannheim = mock_info("lou-mannheim-address", &[]);
+        let msg = ExecuteMsg::ApproveWithdrawal {};
+        let res = execute(deps.as_mut(), env.clone(), lou_mannheim, msg).unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "approve_withdrawal")
+                .add_attribute("guardian", "lou-mannheim-address")
+                .add_attribute("approvals", "2")
+        );
+
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::GetWithdrawalApprovals {},
+        )
+        .unwrap();
+        let value: WithdrawalApprovalsResponse = from_json(&res).unwrap();
+        assert_eq!(
+            value,
+            WithdrawalApprovalsResponse {
+                approvals: vec![
+                    Addr::unchecked("carl-fox-address"),
+                    Addr::unchecked("lou-mannheim-address"),
+                ],
+                threshold: Some(2),
+            }
+        );
+
+        let value: WithdrawalReadyResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::IsWithdrawalReady { beneficiary: None },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(value.is_withdrawal_ready);
+
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // a new withdrawal starts with no approvals
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let res = query(deps.as_ref(), env, QueryMsg::GetWithdrawalApprovals {}).unwrap();
+        let value: WithdrawalApprovalsResponse = from_json(&res).unwrap();
+        assert!(value.approvals.is_empty());
+    }
+
+    #[test]
+    fn emergency_freeze() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        env.block.time = Timestamp::from_seconds(0);
+
+        let funds_sent_to_contract = coins(1_000_000, NATIVE_DENOM);
+
+        let withdraw_address = String::from("gordon-gekko-address");
+
+        // a freeze has to last for something
+        let msg = InstantiateMsg {
+            withdraw_address: withdraw_address.clone(),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: Some(EmergencyCouncilMsg {
+                address: String::from("lou-mannheim-address"),
+                freeze_duration: Duration::Time(0),
+            }),
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &funds_sent_to_contract);
+        let err =
+            instantiate(deps.as_mut(), env.clone(), instantiate_info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidFreezeDuration {});
+
+        let msg = InstantiateMsg {
+            withdraw_address: withdraw_address.clone(),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: Some(EmergencyCouncilMsg {
+                address: String::from("lou-mannheim-address"),
+                freeze_duration: DAY * 7,
+            }),
+            beneficiaries: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // mock funds being added to contract
+        let contract_addr = env.clone().contract.address;
+        deps.querier
+            .update_balance(&contract_addr, funds_sent_to_contract);
+
+        let info = mock_info(&withdraw_address, &[]);
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // only the council can freeze
+        let msg = ExecuteMsg::Freeze {};
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // the council freezes the day before the withdrawal is ready
+        env.block.time = Timestamp::from_seconds(27 * 86400);
+
+        let lou_mannheim = mock_info("lou-mannheim-address", &[]);
+        let msg = ExecuteMsg::Freeze {};
+        execute(deps.as_mut(), env.clone(), lou_mannheim.clone(), msg).unwrap();
+
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::GetFreeze {}).unwrap();
+        let value: FreezeResponse = from_json(&res).unwrap();
+        assert_eq!(
+            value.frozen_until,
+            Some(Expiration::AtTime(Timestamp::from_seconds(34 * 86400)))
+        );
+
+        // roll time forward in env to 1 hr after the 28 day delay
+        env.block.time = Timestamp::from_seconds(28 * 86400).plus_seconds(3600);
+
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::Frozen {});
+
+        // the council cannot extend the freeze
+        let msg = ExecuteMsg::Freeze {};
+        let err = execute(deps.as_mut(), env.clone(), lou_mannheim.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::AlreadyFrozen {});
+
+        // the delay has passed, but the withdrawal is not ready
+        let value: WithdrawalStatusResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::GetWithdrawalStatus { beneficiary: None },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            value.status,
+            WithdrawalStatus::Frozen {
+                until: Expiration::AtTime(Timestamp::from_seconds(34 * 86400)),
+            }
+        );
+        assert_eq!(value.amount, coins(1_000_000, NATIVE_DENOM));
+
+        let value: WithdrawalReadyResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::IsWithdrawalReady { beneficiary: None },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(!value.is_withdrawal_ready);
+
+        // governance is not frozen
+        let msg = SudoMsg::ExecuteSend {
+            recipient: String::from("carl-fox-address"),
+            amount: Uint128::new(1_000),
+            denom: None,
+        };
+        sudo(deps.as_mut(), env.clone(), msg).unwrap();
+
+        // the freeze lifts by itself
+        env.block.time = Timestamp::from_seconds(34 * 86400).plus_seconds(3600);
+
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::GetFreeze {}).unwrap();
+        let value: FreezeResponse = from_json(&res).unwrap();
+        assert_eq!(value.frozen_until, None);
+
+        // nor freeze again straight away
+        let msg = ExecuteMsg::Freeze {};
+        let err = execute(deps.as_mut(), env.clone(), lou_mannheim.clone(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::FreezeCooldown {
+                until: Expiration::AtTime(Timestamp::from_seconds(41 * 86400)),
+            }
+        );
+
+        let value: WithdrawalReadyResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::IsWithdrawalReady { beneficiary: None },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(value.is_withdrawal_ready);
+
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // or governance can lift it sooner
+        let msg = ExecuteMsg::StartWithdraw { amount: None };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        env.block.time = env.block.time.plus_seconds(28 * 86400 + 3600);
+
+        let msg = ExecuteMsg::Freeze {};
+        execute(deps.as_mut(), env.clone(), lou_mannheim, msg).unwrap();
+
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::Frozen {});
+
+        let res = sudo(deps.as_mut(), env.clone(), SudoMsg::Unfreeze {}).unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("message_type", "sudo")
+                .add_attribute("action", "unfreeze")
+        );
+
+        let msg = ExecuteMsg::ExecuteWithdraw {};
+        execute(deps.as_mut(), env, info, msg).unwrap();
+    }
+
+    #[test]
+    fn sudo_set_emergency_council() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let msg = InstantiateMsg {
+            withdraw_address: String::from("gordon-gekko-address"),
+            withdraw_delay: DAY * 28,
+            withdraw_claim_window: None,
+            native_denom: NATIVE_DENOM.to_string(),
+            vesting: None,
+            rewards_destination: None,
+            emergency_council: None,
+            beneficiaries: None,
+        };
+
+        let instantiate_info = mock_info("bud-fox-address", &[]);
+        instantiate(deps.as_mut(), env.clone(), instantiate_info, msg).unwrap();
+
+        // nobody can freeze yet
+        let lou_mannheim = mock_info("lou-mannheim-address", &[]);
+        let msg = ExecuteMsg::Freeze {};
+        let err = execute(deps.as_mut(), env.clone(), lou_mannheim.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let msg = SudoMsg::SetEmergencyCouncil {
+            council: Some(EmergencyCouncilMsg {
+                address: String::from("lou-mannheim-address"),
+                freeze_duration: Duration::Height(1_000),
+            }),
+        };
+        let res = sudo(deps.as_mut(), env.clone(), msg).unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("message_type", "sudo")
+                .add_attribute("action", "set_emergency_council")
+                .add_attribute("emergency_council", "lou-mannheim-address")
+                .add_attribute("freeze_duration", "height: 1000")
+        );
+
+        let msg = ExecuteMsg::Freeze {};
+        execute(deps.as_mut(), env.clone(), lou_mannheim, msg).unwrap();
+
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::GetFreeze {}).unwrap();
+        let value: FreezeResponse = from_json(&res).unwrap();
+        assert_eq!(
+            value.frozen_until,
+            Some(Expiration::AtHeight(env.block.height + 1_000))
+        );
+    }
 }